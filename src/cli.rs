@@ -0,0 +1,260 @@
+use std::{
+    fs,
+    io::{self, Read},
+    path::PathBuf,
+};
+
+use clap::{
+    builder::PossibleValuesParser, builder::TypedValueParser, error::ErrorKind, Args, CommandFactory,
+    Parser, Subcommand,
+};
+use color_eyre::{
+    eyre::{eyre, WrapErr},
+    Result,
+};
+
+use crate::{
+    config::config_file::ConfigFile,
+    features::{
+        history::{
+            history_query::{HistoryQuery, HistorySort},
+            history_report::write_history,
+        },
+        stats::{stats_report::write_stats, stats_summary::StatsSummary},
+        words_challenge::{
+            challenge_mode::ChallengeMode, challenge_settings::ChallengeSettings,
+            custom_text::CustomText, quote::QuoteLength, word_generator::WordBand,
+        },
+    },
+    storage::database::Database,
+    tui::{message::Message, tui::run_tui},
+};
+
+// reading from this path, or passing it on its own, reads the text from stdin
+const STDIN_PATH: &str = "-";
+
+#[derive(Debug, Parser)]
+#[command(version, about = "A typing test for the terminal")]
+pub struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Practise on the text in a file instead of random words, - reads it from stdin"
+    )]
+    file: Option<PathBuf>,
+
+    #[arg(
+        value_name = "-",
+        value_parser = [STDIN_PATH],
+        conflicts_with = "file",
+        help = "Practise on text read from stdin, the same as --file -"
+    )]
+    stdin: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "WORDS",
+        value_parser = clap::value_parser!(u32).range(1..),
+        help = "Split the text into challenges of this many words, typed one after another"
+    )]
+    chunk: Option<u32>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "NAME",
+        help = "Draw everything in this theme instead of the one in the config file"
+    )]
+    theme: Option<String>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    #[command(about = "Start a test of a set number of words")]
+    Words {
+        #[arg(
+            default_value_t = 25,
+            value_parser = clap::value_parser!(u32).range(1..),
+            help = "How many words to type"
+        )]
+        count: u32,
+
+        #[command(flatten)]
+        words: WordsArgs,
+    },
+    #[command(about = "Start a test against the clock")]
+    Time {
+        #[arg(
+            default_value_t = 30,
+            value_parser = clap::value_parser!(u64).range(1..),
+            help = "How many seconds the test lasts"
+        )]
+        seconds: u64,
+
+        #[command(flatten)]
+        words: WordsArgs,
+    },
+    #[command(about = "Start a test typing a quote")]
+    Quote {
+        #[arg(
+            long,
+            default_value = "medium",
+            value_parser = PossibleValuesParser::new(["short", "medium", "long", "thicc"])
+                .try_map(|name| name.parse::<QuoteLength>()),
+            help = "How long the quote is"
+        )]
+        length: QuoteLength,
+
+        #[arg(long, help = "Pick the quote with this seed, the same seed always picks the same quote")]
+        seed: Option<u64>,
+    },
+    #[command(about = "Practise the letters that need it most, unlocking more as they improve")]
+    Adaptive {
+        #[arg(
+            default_value_t = ChallengeMode::ADAPTIVE_WORD_COUNT as u32,
+            value_parser = clap::value_parser!(u32).range(1..),
+            help = "How many words each lesson has"
+        )]
+        count: u32,
+    },
+    #[command(about = "Print past results")]
+    History {
+        #[arg(long, default_value_t = 20, help = "How many results to print")]
+        limit: usize,
+
+        #[arg(
+            long,
+            default_value = "date",
+            value_parser = PossibleValuesParser::new(["date", "wpm", "accuracy"])
+                .try_map(|name| name.parse::<HistorySort>()),
+            help = "What the results are ordered by, highest first"
+        )]
+        sort: HistorySort,
+    },
+    #[command(about = "Print totals, averages and personal bests")]
+    Stats,
+}
+
+// What words and timed tests are made of
+#[derive(Debug, Args)]
+struct WordsArgs {
+    #[arg(long, help = "Generate the words from this seed, the same seed always gives the same words")]
+    seed: Option<u64>,
+
+    #[arg(
+        long,
+        default_value = "english",
+        value_parser = PossibleValuesParser::new(WordBand::ALL.map(|band| band.language_name()))
+            .try_map(|name| name.parse::<WordBand>()),
+        help = "Which word list the words are drawn from. They're all english and only differ in \
+            size, the 200, 1,000 or 10,000 most common words"
+    )]
+    language: WordBand,
+}
+
+impl Cli {
+    // starts the app, or prints what a non-interactive command asked for
+    pub fn run(self) -> Result<()> {
+        // the text to practise on only makes sense without a command, but the theme goes with
+        // any of them so clap can't be left to reject every argument given with a command
+        let has_custom_text = self.file.is_some() || self.stdin.is_some() || self.chunk.is_some();
+        if self.command.is_some() && has_custom_text {
+            Cli::command()
+                .error(
+                    ErrorKind::ArgumentConflict,
+                    "--file, - and --chunk can't be used with a command",
+                )
+                .exit();
+        }
+        // chunking is only done to text from a file or stdin, it shouldn't look like it's on
+        if self.chunk.is_some() && self.file.is_none() && self.stdin.is_none() {
+            Cli::command()
+                .error(ErrorKind::MissingRequiredArgument, "--chunk needs --file or -")
+                .exit();
+        }
+
+        let mut config_file = ConfigFile::default_location().with_theme_override(self.theme.clone());
+        let config = config_file.load()?;
+        let database = Database::open(config.data_dir.as_deref())?;
+
+        let (settings, seed) = match self.command {
+            None => {
+                let custom_text = self.custom_text()?;
+                let first_msg = custom_text.as_ref().map(|_| Message::StartNextCustomChunk);
+                return run_tui(database, config_file, config, custom_text, first_msg);
+            }
+            Some(Command::History { limit, sort }) => {
+                let query = HistoryQuery {
+                    sort,
+                    limit: Some(limit),
+                    ..Default::default()
+                };
+                let rows = database.load_result_summaries(&query)?;
+                return Ok(write_history(&rows, &mut io::stdout().lock())?);
+            }
+            Some(Command::Stats) => {
+                let rows = database.load_result_summaries(&HistoryQuery::default())?;
+                let stats = StatsSummary::from_results(&rows);
+                let key_stats = database.load_key_stats()?;
+                return Ok(write_stats(
+                    stats.as_ref(),
+                    &key_stats,
+                    &config.keyboard_layout,
+                    &mut io::stdout().lock(),
+                )?);
+            }
+            Some(Command::Words { count, words }) => (
+                ChallengeSettings::new(ChallengeMode::Words(count as usize), words.language),
+                words.seed,
+            ),
+            Some(Command::Time { seconds, words }) => (
+                ChallengeSettings::new(ChallengeMode::Time(seconds), words.language),
+                words.seed,
+            ),
+            Some(Command::Quote { length, seed }) => (
+                ChallengeSettings::new(ChallengeMode::Quote(length), WordBand::Top200),
+                seed,
+            ),
+            Some(Command::Adaptive { count }) => (
+                ChallengeSettings::new(ChallengeMode::Adaptive(count as usize), WordBand::Top200),
+                None,
+            ),
+        };
+
+        let seed = seed.unwrap_or_else(rand::random);
+        let first_msg = Message::StartChallenge(settings, seed);
+        run_tui(database, config_file, config, None, Some(first_msg))
+    }
+
+    // Reads the text to practise on, if any was asked for. This has to happen before the
+    // terminal is taken over, as once it is stdin can't be read to the end
+    fn custom_text(&self) -> Result<Option<CustomText>> {
+        let path = match (&self.file, &self.stdin) {
+            (Some(path), _) => path.clone(),
+            (None, Some(_)) => PathBuf::from(STDIN_PATH),
+            (None, None) => return Ok(None),
+        };
+
+        let from_stdin = path.as_os_str() == STDIN_PATH;
+        let raw_text = if from_stdin {
+            let mut raw_text = String::new();
+            io::stdin()
+                .read_to_string(&mut raw_text)
+                .wrap_err("failed to read text from stdin")?;
+            raw_text
+        } else {
+            fs::read_to_string(&path)
+                .wrap_err_with(|| format!("failed to read text from {}", path.display()))?
+        };
+
+        let chunk_size = self.chunk.map(|chunk| chunk as usize);
+        match CustomText::new(&raw_text, chunk_size) {
+            Some(custom_text) => Ok(Some(custom_text)),
+            None if from_stdin => Err(eyre!("there is no text to practise on in stdin")),
+            None => Err(eyre!("there is no text to practise on in {}", path.display())),
+        }
+    }
+}
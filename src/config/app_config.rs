@@ -0,0 +1,304 @@
+use std::{ops::Range, path::PathBuf};
+
+use color_eyre::Result;
+use serde::Deserialize;
+use toml::{Spanned, Value};
+
+use crate::features::{
+    keyboard::keyboard_layout::KeyboardLayout,
+    words_challenge::{
+        backspace_policy::BackspacePolicy, caret_style::CaretStyle, challenge_mode::ChallengeMode,
+        fail_conditions::FailConditions, quote::QuoteLength, stop_on_error::StopOnError,
+    },
+};
+
+use super::{
+    keybindings::Keybindings,
+    theme::Theme,
+    toml_error::{line_error, toml_error},
+};
+
+// Everything the config file controls, anything left out of the file keeps its default
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppConfig {
+    // the mode picked out on the menu when it opens
+    pub default_mode: ChallengeMode,
+    pub theme: Theme,
+    pub caret_style: CaretStyle,
+    pub backspace_policy: BackspacePolicy,
+    pub stop_on_error: StopOnError,
+    pub fail_conditions: FailConditions,
+    // whether a keyboard showing the next key to press is drawn under the text
+    pub show_keyboard: bool,
+    // the layout typing is read as, keys pressed on a qwerty keyboard are swapped for the key in
+    // the same place on this one
+    pub keyboard_layout: KeyboardLayout,
+    pub keybindings: Keybindings,
+    // where results are saved instead of the user's data directory, this is only read when the
+    // app starts as the database is kept open while it runs
+    pub data_dir: Option<PathBuf>,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            default_mode: ChallengeMode::Words(25),
+            theme: Theme::default(),
+            caret_style: CaretStyle::default(),
+            backspace_policy: BackspacePolicy::default(),
+            stop_on_error: StopOnError::default(),
+            fail_conditions: FailConditions::default(),
+            show_keyboard: false,
+            keyboard_layout: KeyboardLayout::default(),
+            keybindings: Keybindings::default(),
+            data_dir: None,
+        }
+    }
+}
+
+// The config file as it's written, the length is checked against the mode once both are read
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigToml {
+    mode: Option<ModeName>,
+    length: Option<Spanned<Value>>,
+    theme: Option<Spanned<String>>,
+    caret_style: Option<CaretStyle>,
+    backspace: Option<BackspacePolicy>,
+    stop_on_error: Option<StopOnError>,
+    #[serde(default)]
+    fail: FailConditions,
+    keyboard: Option<bool>,
+    layout: Option<Spanned<String>>,
+    #[serde(default)]
+    keybindings: Keybindings,
+    data_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ModeName {
+    Words,
+    Time,
+    Quote,
+    Adaptive,
+}
+
+impl AppConfig {
+    // reads the contents of a config file, picking its theme and layout out of the given ones,
+    // any error says which line it's on
+    pub fn from_toml(text: &str, themes: &[Theme], layouts: &[KeyboardLayout]) -> Result<Self> {
+        let config: ConfigToml = toml::from_str(text).map_err(|error| toml_error(text, error))?;
+
+        let default_mode = default_mode(config.mode, config.length.as_ref())
+            .map_err(|(span, message)| line_error(text, span, message))?;
+
+        let theme = match config.theme {
+            Some(name) => Theme::find(themes, name.get_ref())
+                .map_err(|error| line_error(text, name.span(), error))?,
+            None => Theme::default(),
+        };
+
+        let keyboard_layout = match config.layout {
+            Some(name) => KeyboardLayout::find(layouts, name.get_ref())
+                .map_err(|error| line_error(text, name.span(), error))?,
+            None => KeyboardLayout::default(),
+        };
+
+        Ok(Self {
+            default_mode,
+            theme,
+            caret_style: config.caret_style.unwrap_or_default(),
+            backspace_policy: config.backspace.unwrap_or_default(),
+            stop_on_error: config.stop_on_error.unwrap_or_default(),
+            fail_conditions: config.fail,
+            show_keyboard: config.keyboard.unwrap_or_default(),
+            keyboard_layout,
+            keybindings: config.keybindings,
+            data_dir: config.data_dir.map(expand_home),
+        })
+    }
+}
+
+// The mode a length is given for defaults to words. Lengths are limited to the ones the menu
+// offers so that the default mode can always be picked out on it
+fn default_mode(
+    mode: Option<ModeName>,
+    length: Option<&Spanned<Value>>,
+) -> Result<ChallengeMode, (Range<usize>, String)> {
+    let mode = mode.unwrap_or(ModeName::Words);
+    let Some(length) = length else {
+        return Ok(match mode {
+            ModeName::Words => ChallengeMode::Words(25),
+            ModeName::Time => ChallengeMode::Time(30),
+            ModeName::Quote => ChallengeMode::Quote(QuoteLength::Medium),
+            ModeName::Adaptive => ChallengeMode::Adaptive(ChallengeMode::ADAPTIVE_WORD_COUNT),
+        });
+    };
+
+    let challenge_mode = match (mode, length.get_ref()) {
+        (ModeName::Words, Value::Integer(count)) => ChallengeMode::WORD_COUNTS
+            .into_iter()
+            .find(|word_count| *word_count as i64 == *count)
+            .map(ChallengeMode::Words),
+        (ModeName::Time, Value::Integer(seconds)) => ChallengeMode::TIME_LIMITS
+            .into_iter()
+            .find(|time_limit| *time_limit as i64 == *seconds)
+            .map(ChallengeMode::Time),
+        (ModeName::Quote, Value::String(name)) => name.parse().ok().map(ChallengeMode::Quote),
+        (ModeName::Adaptive, Value::Integer(count))
+            if *count == ChallengeMode::ADAPTIVE_WORD_COUNT as i64 =>
+        {
+            Some(ChallengeMode::Adaptive(ChallengeMode::ADAPTIVE_WORD_COUNT))
+        }
+        _ => None,
+    };
+
+    challenge_mode.ok_or_else(|| {
+        let expected = match mode {
+            ModeName::Words => list(&ChallengeMode::WORD_COUNTS),
+            ModeName::Time => list(&ChallengeMode::TIME_LIMITS),
+            ModeName::Quote => list(&QuoteLength::ALL.map(|length| format!("\"{}\"", length))),
+            ModeName::Adaptive => list(&[ChallengeMode::ADAPTIVE_WORD_COUNT]),
+        };
+        (length.span(), format!("length should be one of {}", expected))
+    })
+}
+
+fn list(values: &[impl ToString]) -> String {
+    let values: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+    values.join(", ")
+}
+
+// paths in the config file can start with ~ for the home directory, as they would in a shell
+fn expand_home(path: PathBuf) -> PathBuf {
+    match (path.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(relative_path), Some(home)) => home.join(relative_path),
+        _ => path,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyCode;
+
+    use super::*;
+
+    fn parse(text: &str) -> Result<AppConfig> {
+        AppConfig::from_toml(text, &Theme::built_in(), &KeyboardLayout::built_in())
+    }
+
+    #[test]
+    fn empty_file_gives_the_defaults() {
+        assert_eq!(parse("").unwrap(), AppConfig::default());
+    }
+
+    #[test]
+    fn every_key_can_be_set() {
+        let config = parse(
+            r#"
+            mode = "time"
+            length = 60
+            caret_style = "underline"
+            backspace = "within_word"
+            stop_on_error = "word"
+            keyboard = true
+            layout = "dvorak"
+            data_dir = "/tmp/typee"
+
+            theme = "nord"
+
+            [fail]
+            min_accuracy = 90
+            min_wpm = 40
+            min_wpm_seconds = 3
+
+            [keybindings]
+            retry = "tab"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.default_mode, ChallengeMode::Time(60));
+        assert_eq!(config.theme.name, "nord");
+        assert_eq!(config.caret_style, CaretStyle::Underline);
+        assert_eq!(config.backspace_policy, BackspacePolicy::WithinWord);
+        assert_eq!(config.stop_on_error, StopOnError::Word);
+        assert_eq!(config.fail_conditions.min_accuracy, Some(90.0));
+        assert_eq!(config.fail_conditions.min_wpm, Some(40.0));
+        assert_eq!(config.fail_conditions.min_wpm_seconds, 3);
+        assert!(!config.fail_conditions.sudden_death);
+        assert!(config.show_keyboard);
+        assert_eq!(config.keyboard_layout.name, "dvorak");
+        assert_eq!(config.data_dir, Some(PathBuf::from("/tmp/typee")));
+        assert_eq!(config.keybindings.retry, KeyCode::Tab);
+    }
+
+    #[test]
+    fn quote_length_is_given_by_name() {
+        let config = parse("mode = \"quote\"\nlength = \"long\"").unwrap();
+
+        assert_eq!(config.default_mode, ChallengeMode::Quote(QuoteLength::Long));
+    }
+
+    #[test]
+    fn unknown_key_error_points_at_its_line() {
+        let error = parse("mode = \"words\"\n\ncolour = \"red\"").unwrap_err();
+
+        assert!(error.to_string().starts_with("line 3: unknown field `colour`"));
+    }
+
+    #[test]
+    fn length_that_doesnt_fit_the_mode_points_at_its_line() {
+        let error = parse("mode = \"time\"\nlength = 45").unwrap_err();
+
+        assert_eq!(error.to_string(), "line 2: length should be one of 15, 30, 60, 120");
+    }
+
+    #[test]
+    fn home_is_expanded_in_data_dir() {
+        let config = parse("data_dir = \"~/typee\"").unwrap();
+
+        assert_eq!(config.data_dir, dirs::home_dir().map(|home| home.join("typee")));
+    }
+
+    #[test]
+    fn quit_key_used_for_typing_points_at_its_line() {
+        let error = parse("[keybindings]\nquit = \"enter\"").unwrap_err();
+
+        assert!(error
+            .to_string()
+            .starts_with("line 2: quit can't be a key used for typing or moving around"));
+    }
+
+    #[test]
+    fn keybinding_clashing_with_a_page_key_points_at_its_line() {
+        let error = parse("[keybindings]\nretry = \"t\"\nrace = \"s\"").unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "line 3: 's' is already used on the history page, try another key"
+        );
+    }
+
+    #[test]
+    fn fail_limits_out_of_range_point_at_their_line() {
+        let errors = [
+            ("[fail]\nmin_accuracy = 150", "line 2: min_accuracy should be between 0 and 100"),
+            ("[fail]\nmin_accuracy = -5", "line 2: min_accuracy should be between 0 and 100"),
+            ("[fail]\n\nmin_wpm = 0", "line 3: min_wpm should be more than 0"),
+            ("[fail]\nmin_wpm_seconds = 0", "line 2: min_wpm_seconds should be at least 1"),
+        ];
+
+        for (text, message) in errors {
+            assert_eq!(parse(text).unwrap_err().to_string(), message);
+        }
+    }
+
+    #[test]
+    fn unknown_theme_points_at_its_line() {
+        let error = parse("\ntheme = \"neon\"").unwrap_err();
+
+        assert!(error.to_string().starts_with("line 2: unknown theme 'neon'"));
+    }
+}
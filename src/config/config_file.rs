@@ -0,0 +1,121 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use color_eyre::{eyre::WrapErr, Result};
+
+use crate::features::keyboard::keyboard_layout::KeyboardLayout;
+
+use super::{app_config::AppConfig, theme::Theme};
+
+const CONFIG_FILE_NAME: &str = "config.toml";
+const THEMES_DIRECTORY_NAME: &str = "themes";
+const LAYOUTS_DIRECTORY_NAME: &str = "layouts";
+
+// The config file, theme files and layout files on disk, remembering when they were last loaded
+// so that changes to them can be picked up while the app is running
+pub struct ConfigFile {
+    // there's nowhere to look when the user has no config directory
+    directory: Option<PathBuf>,
+    // a theme given on the command line, which wins over the one in the file
+    theme_override: Option<String>,
+    loaded_files: Vec<(PathBuf, SystemTime)>,
+}
+
+impl ConfigFile {
+    // the files in the user's config directory, e.g. ~/.config/typee/config.toml,
+    // ~/.config/typee/themes/*.toml and ~/.config/typee/layouts/*.toml on linux
+    pub fn default_location() -> Self {
+        Self {
+            directory: dirs::config_dir().map(|directory| directory.join("typee")),
+            theme_override: None,
+            loaded_files: Vec::new(),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn in_directory(directory: PathBuf) -> Self {
+        Self {
+            directory: Some(directory),
+            theme_override: None,
+            loaded_files: Vec::new(),
+        }
+    }
+
+    pub fn with_theme_override(self, theme_override: Option<String>) -> Self {
+        Self {
+            theme_override,
+            ..self
+        }
+    }
+
+    // reads the config, the defaults are used when the file doesn't exist
+    pub fn load(&mut self) -> Result<AppConfig> {
+        self.loaded_files = self.watched_files();
+
+        let themes = Theme::load_all(self.themes_directory().as_deref())?;
+        let layouts = KeyboardLayout::load_all(self.layouts_directory().as_deref())?;
+
+        let config = match self.config_path().filter(|path| path.exists()) {
+            Some(path) => {
+                let text = fs::read_to_string(&path)
+                    .wrap_err_with(|| format!("failed to read config file {}", path.display()))?;
+                AppConfig::from_toml(&text, &themes, &layouts)
+                    .wrap_err_with(|| format!("invalid config file {}", path.display()))?
+            }
+            None => AppConfig::default(),
+        };
+
+        match &self.theme_override {
+            Some(name) => Ok(AppConfig {
+                theme: Theme::find(&themes, name)?,
+                ..config
+            }),
+            None => Ok(config),
+        }
+    }
+
+    // whether any of the files have been edited, created or removed since they were last loaded
+    pub fn has_changed(&self) -> bool {
+        self.watched_files() != self.loaded_files
+    }
+
+    fn config_path(&self) -> Option<PathBuf> {
+        Some(self.directory.as_ref()?.join(CONFIG_FILE_NAME))
+    }
+
+    fn themes_directory(&self) -> Option<PathBuf> {
+        Some(self.directory.as_ref()?.join(THEMES_DIRECTORY_NAME))
+    }
+
+    fn layouts_directory(&self) -> Option<PathBuf> {
+        Some(self.directory.as_ref()?.join(LAYOUTS_DIRECTORY_NAME))
+    }
+
+    // the config file and every theme and layout file with when they were last modified
+    fn watched_files(&self) -> Vec<(PathBuf, SystemTime)> {
+        let directory_paths = [self.themes_directory(), self.layouts_directory()]
+            .into_iter()
+            .flatten()
+            .filter_map(|directory| fs::read_dir(directory).ok())
+            .flatten()
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()));
+
+        let mut files: Vec<(PathBuf, SystemTime)> = self
+            .config_path()
+            .into_iter()
+            .chain(directory_paths)
+            .filter_map(|path| modified(&path).map(|modified| (path, modified)))
+            .collect();
+        files.sort();
+        files
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
@@ -0,0 +1,264 @@
+use crossterm::event::KeyCode;
+use serde::{de, Deserialize, Deserializer};
+
+// The keys for everything other than typing that can be changed in the config file. Each key is
+// written as a single character or the name of a special key, e.g. "r", "enter" or "f5"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Keybindings {
+    // quitting works from every page, including while typing, so it can't be a key that types
+    // or moves around a page
+    #[serde(deserialize_with = "deserialize_quit_key")]
+    pub quit: KeyCode,
+    #[serde(deserialize_with = "deserialize_key")]
+    pub retry: KeyCode,
+    #[serde(deserialize_with = "deserialize_key")]
+    pub new_test: KeyCode,
+    #[serde(deserialize_with = "deserialize_key")]
+    pub replay: KeyCode,
+    // starts a challenge made of the words the last one had mistakes in
+    #[serde(deserialize_with = "deserialize_key")]
+    pub practise_missed: KeyCode,
+    // the menu and history keys are also used on the history and menu pages, and racing is done
+    // from the history, so they can't be one of those pages' own keys
+    #[serde(deserialize_with = "deserialize_history_page_key")]
+    pub menu: KeyCode,
+    #[serde(deserialize_with = "deserialize_menu_page_key")]
+    pub history: KeyCode,
+    // races against the result selected in the history
+    #[serde(deserialize_with = "deserialize_history_page_key")]
+    pub race: KeyCode,
+    // switches the keyboard heatmap between error rate and speed
+    #[serde(deserialize_with = "deserialize_key")]
+    pub heatmap: KeyCode,
+}
+
+impl Default for Keybindings {
+    fn default() -> Self {
+        Self {
+            quit: KeyCode::Esc,
+            retry: KeyCode::Char('r'),
+            new_test: KeyCode::Char('n'),
+            replay: KeyCode::Char('p'),
+            practise_missed: KeyCode::Char('w'),
+            menu: KeyCode::Char('m'),
+            history: KeyCode::Char('h'),
+            race: KeyCode::Char('r'),
+            heatmap: KeyCode::Tab,
+        }
+    }
+}
+
+// the keys the menu and history pages use for themselves, which can't be changed
+const MENU_PAGE_KEYS: &[KeyCode] = &[
+    KeyCode::Enter,
+    KeyCode::Up,
+    KeyCode::Down,
+    KeyCode::Left,
+    KeyCode::Right,
+    KeyCode::Tab,
+    KeyCode::Char('j'),
+    KeyCode::Char('k'),
+    KeyCode::Char('g'),
+    KeyCode::Char('s'),
+    KeyCode::Char('/'),
+];
+const HISTORY_PAGE_KEYS: &[KeyCode] = &[
+    KeyCode::Enter,
+    KeyCode::Up,
+    KeyCode::Down,
+    KeyCode::PageUp,
+    KeyCode::PageDown,
+    KeyCode::Home,
+    KeyCode::End,
+    KeyCode::Char('j'),
+    KeyCode::Char('k'),
+    KeyCode::Char('g'),
+    KeyCode::Char('G'),
+    KeyCode::Char('d'),
+    KeyCode::Char('s'),
+    KeyCode::Char('f'),
+    KeyCode::Char('l'),
+];
+
+// the name a key is written as in the config file, which is also how it's shown in help lines
+pub fn key_name(key: KeyCode) -> String {
+    match key {
+        KeyCode::Char(' ') => "space".to_string(),
+        KeyCode::Char(char) => char.to_string(),
+        KeyCode::F(number) => format!("f{}", number),
+        KeyCode::Esc => "esc".to_string(),
+        KeyCode::Enter => "enter".to_string(),
+        KeyCode::Tab => "tab".to_string(),
+        KeyCode::Backspace => "backspace".to_string(),
+        KeyCode::Delete => "delete".to_string(),
+        KeyCode::Insert => "insert".to_string(),
+        KeyCode::Home => "home".to_string(),
+        KeyCode::End => "end".to_string(),
+        KeyCode::PageUp => "pageup".to_string(),
+        KeyCode::PageDown => "pagedown".to_string(),
+        KeyCode::Up => "up".to_string(),
+        KeyCode::Down => "down".to_string(),
+        KeyCode::Left => "left".to_string(),
+        KeyCode::Right => "right".to_string(),
+        _ => "?".to_string(),
+    }
+}
+
+fn parse_key(name: &str) -> Option<KeyCode> {
+    let mut chars = name.chars();
+    if let (Some(char), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(char));
+    }
+
+    let key = match name.to_lowercase().as_str() {
+        "space" => KeyCode::Char(' '),
+        "esc" | "escape" => KeyCode::Esc,
+        "enter" | "return" => KeyCode::Enter,
+        "tab" => KeyCode::Tab,
+        "backspace" => KeyCode::Backspace,
+        "delete" => KeyCode::Delete,
+        "insert" => KeyCode::Insert,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        function_key => {
+            let number = function_key.strip_prefix('f')?.parse().ok()?;
+            if !(1..=12).contains(&number) {
+                return None;
+            }
+            KeyCode::F(number)
+        }
+    };
+
+    Some(key)
+}
+
+fn deserialize_key<'de, D: Deserializer<'de>>(deserializer: D) -> Result<KeyCode, D::Error> {
+    let name = String::deserialize(deserializer)?;
+
+    parse_key(&name).ok_or_else(|| de::Error::custom(format!("unknown key '{}'", name)))
+}
+
+fn deserialize_menu_page_key<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<KeyCode, D::Error> {
+    deserialize_page_key(deserializer, "menu", MENU_PAGE_KEYS)
+}
+
+fn deserialize_history_page_key<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<KeyCode, D::Error> {
+    deserialize_page_key(deserializer, "history", HISTORY_PAGE_KEYS)
+}
+
+// a key used on a page that has keys of its own, it would hide one of them otherwise
+fn deserialize_page_key<'de, D: Deserializer<'de>>(
+    deserializer: D,
+    page: &str,
+    page_keys: &[KeyCode],
+) -> Result<KeyCode, D::Error> {
+    let key = deserialize_key(deserializer)?;
+    if page_keys.contains(&key) {
+        return Err(de::Error::custom(format!(
+            "'{}' is already used on the {} page, try another key",
+            key_name(key),
+            page
+        )));
+    }
+
+    Ok(key)
+}
+
+fn deserialize_quit_key<'de, D: Deserializer<'de>>(deserializer: D) -> Result<KeyCode, D::Error> {
+    match deserialize_key(deserializer)? {
+        KeyCode::Char(_)
+        | KeyCode::Backspace
+        | KeyCode::Enter
+        | KeyCode::Tab
+        | KeyCode::Up
+        | KeyCode::Down
+        | KeyCode::Left
+        | KeyCode::Right
+        | KeyCode::Home
+        | KeyCode::End
+        | KeyCode::PageUp
+        | KeyCode::PageDown => Err(de::Error::custom(
+            "quit can't be a key used for typing or moving around, try a key like esc or f10",
+        )),
+        key => Ok(key),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_names_parse_back_to_their_key() {
+        let keys = [
+            KeyCode::Char('r'),
+            KeyCode::Char(' '),
+            KeyCode::Esc,
+            KeyCode::Enter,
+            KeyCode::F(10),
+            KeyCode::PageDown,
+        ];
+
+        for key in keys {
+            assert_eq!(parse_key(&key_name(key)), Some(key));
+        }
+    }
+
+    #[test]
+    fn unknown_key_names_dont_parse() {
+        assert_eq!(parse_key("hyper"), None);
+        assert_eq!(parse_key("f13"), None);
+        assert_eq!(parse_key(""), None);
+    }
+
+    #[test]
+    fn missing_keybindings_keep_their_defaults() {
+        let keybindings: Keybindings = toml::from_str("retry = \"tab\"").unwrap();
+
+        assert_eq!(keybindings.retry, KeyCode::Tab);
+        assert_eq!(keybindings.quit, KeyCode::Esc);
+    }
+
+    #[test]
+    fn quit_cant_be_a_typed_character() {
+        let result = toml::from_str::<Keybindings>("quit = \"q\"");
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn keys_used_on_the_menu_or_history_cant_clash_with_their_own_keys() {
+        for text in ["race = \"s\"", "menu = \"j\"", "history = \"/\"", "race = \"enter\""] {
+            let result = toml::from_str::<Keybindings>(text);
+
+            assert!(result.is_err(), "{} should be rejected", text);
+        }
+
+        let keybindings: Keybindings = toml::from_str("race = \"x\"\nhistory = \"d\"").unwrap();
+        assert_eq!(keybindings.race, KeyCode::Char('x'));
+        assert_eq!(keybindings.history, KeyCode::Char('d'));
+    }
+
+    #[test]
+    fn quit_cant_be_a_key_for_typing_or_moving_around() {
+        for name in ["space", "backspace", "enter", "tab", "up", "pagedown", "home"] {
+            let result = toml::from_str::<Keybindings>(&format!("quit = \"{}\"", name));
+
+            assert!(result.is_err(), "quit = {} should be rejected", name);
+        }
+
+        let keybindings: Keybindings = toml::from_str("quit = \"f10\"").unwrap();
+        assert_eq!(keybindings.quit, KeyCode::F(10));
+    }
+}
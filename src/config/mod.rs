@@ -0,0 +1,5 @@
+pub(crate) mod app_config;
+pub(crate) mod config_file;
+pub(crate) mod keybindings;
+pub(crate) mod theme;
+pub(crate) mod toml_error;
@@ -0,0 +1,228 @@
+use std::{fs, path::Path};
+
+use color_eyre::{
+    eyre::{eyre, WrapErr},
+    Result,
+};
+use ratatui::style::{palette::tailwind, Color};
+use serde::Deserialize;
+
+use super::toml_error::toml_error;
+
+// Themes that come with the app, written the same way as a user's own theme files
+const BUILT_IN_THEMES: [(&str, &str); 4] = [
+    ("dracula", include_str!("themes/dracula.toml")),
+    ("gruvbox", include_str!("themes/gruvbox.toml")),
+    ("nord", include_str!("themes/nord.toml")),
+    ("serika", include_str!("themes/serika.toml")),
+];
+
+const DEFAULT_THEME_NAME: &str = "default";
+
+// The colours everything is drawn in. Colours are written as names like "red", hex like
+// "#fde047" or "reset" for the terminal's own colour, anything left out of a theme file keeps
+// the colour of the default theme
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Theme {
+    // taken from the file name rather than written in the file
+    #[serde(skip)]
+    pub name: String,
+    pub background: Color,
+    pub foreground: Color,
+    // titles, selected items and anything else that should stand out
+    pub accent: Color,
+    // items in lists and tables that aren't selected
+    pub secondary: Color,
+    // hints and the labels next to values
+    pub label: Color,
+    pub warning: Color,
+    // the text being typed
+    pub correct: Color,
+    pub incorrect: Color,
+    // letters typed past the end of a word
+    pub extra: Color,
+    // letters skipped over by pressing space partway through a word, these are also underlined
+    pub missed: Color,
+    pub untyped: Color,
+    pub untyped_background: Color,
+    pub caret: Color,
+    pub ghost: Color,
+    pub ghost_background: Color,
+    // the progress shown above the text and how far ahead of or behind the ghost the caret is
+    pub hud: Color,
+    pub ahead: Color,
+    pub behind: Color,
+    // the wpm chart on the results
+    pub chart_wpm: Color,
+    pub chart_raw: Color,
+    pub chart_errors: Color,
+    // the labels on keys drawn in one of the colours below
+    pub key_text: Color,
+    // the keyboard heatmap, keys are coloured from good to poor
+    pub heatmap_good: Color,
+    pub heatmap_fair: Color,
+    pub heatmap_poor: Color,
+    // the keys of the on-screen keyboard, by the finger that presses them
+    pub finger_pinky: Color,
+    pub finger_ring: Color,
+    pub finger_middle: Color,
+    pub finger_index: Color,
+    pub finger_thumb: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            name: DEFAULT_THEME_NAME.to_string(),
+            background: Color::Reset,
+            foreground: Color::Reset,
+            accent: tailwind::YELLOW.c300,
+            secondary: tailwind::GRAY.c400,
+            label: tailwind::GRAY.c500,
+            warning: tailwind::RED.c500,
+            correct: tailwind::GRAY.c800,
+            incorrect: tailwind::RED.c500,
+            extra: tailwind::RED.c800,
+            missed: tailwind::RED.c500,
+            untyped: tailwind::YELLOW.c300,
+            untyped_background: tailwind::GRAY.c100,
+            caret: tailwind::YELLOW.c300,
+            ghost: tailwind::GRAY.c300,
+            ghost_background: tailwind::GRAY.c500,
+            hud: tailwind::YELLOW.c300,
+            ahead: tailwind::GREEN.c400,
+            behind: tailwind::RED.c500,
+            chart_wpm: tailwind::YELLOW.c300,
+            chart_raw: tailwind::GRAY.c500,
+            chart_errors: tailwind::RED.c500,
+            key_text: tailwind::GRAY.c900,
+            heatmap_good: tailwind::GREEN.c400,
+            heatmap_fair: tailwind::YELLOW.c300,
+            heatmap_poor: tailwind::RED.c500,
+            finger_pinky: tailwind::PURPLE.c400,
+            finger_ring: tailwind::SKY.c400,
+            finger_middle: tailwind::GREEN.c400,
+            finger_index: tailwind::ORANGE.c400,
+            finger_thumb: tailwind::GRAY.c400,
+        }
+    }
+}
+
+impl Theme {
+    pub fn from_toml(name: &str, text: &str) -> Result<Self> {
+        let theme: Theme = toml::from_str(text).map_err(|error| toml_error(text, error))?;
+
+        Ok(Self {
+            name: name.to_string(),
+            ..theme
+        })
+    }
+
+    // the default theme and the others that come with the app
+    pub fn built_in() -> Vec<Theme> {
+        let bundled_themes = BUILT_IN_THEMES.iter().map(|(name, text)| {
+            Self::from_toml(name, text).expect("built in themes should be valid")
+        });
+
+        [Theme::default()]
+            .into_iter()
+            .chain(bundled_themes)
+            .collect()
+    }
+
+    // Every built in theme and then each .toml file in the given directory, named after the
+    // file. A user's theme with the same name as a built in one replaces it
+    pub fn load_all(themes_directory: Option<&Path>) -> Result<Vec<Theme>> {
+        let mut themes = Self::built_in();
+
+        let Some(themes_directory) = themes_directory.filter(|directory| directory.is_dir()) else {
+            return Ok(themes);
+        };
+
+        let mut paths: Vec<_> = fs::read_dir(themes_directory)
+            .wrap_err_with(|| format!("failed to read themes from {}", themes_directory.display()))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension == "toml")
+            })
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            let name = path.file_stem().unwrap_or_default().to_string_lossy();
+            let text = fs::read_to_string(&path)
+                .wrap_err_with(|| format!("failed to read theme file {}", path.display()))?;
+            let theme = Self::from_toml(&name, &text)
+                .wrap_err_with(|| format!("invalid theme file {}", path.display()))?;
+
+            themes.retain(|existing| existing.name != theme.name);
+            themes.push(theme);
+        }
+
+        Ok(themes)
+    }
+
+    pub fn find(themes: &[Theme], name: &str) -> Result<Theme> {
+        themes
+            .iter()
+            .find(|theme| theme.name == name)
+            .cloned()
+            .ok_or_else(|| {
+                let names: Vec<&str> = themes.iter().map(|theme| theme.name.as_str()).collect();
+                eyre!(
+                    "unknown theme '{}', expected one of {}",
+                    name,
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn built_in_themes_are_named_after_their_files() {
+        let names: Vec<String> = Theme::built_in()
+            .into_iter()
+            .map(|theme| theme.name)
+            .collect();
+
+        assert_eq!(
+            names,
+            vec!["default", "dracula", "gruvbox", "nord", "serika"]
+        );
+    }
+
+    #[test]
+    fn colours_left_out_keep_the_default() {
+        let theme =
+            Theme::from_toml("mine", "correct = \"#00ff00\"\nincorrect = \"magenta\"").unwrap();
+
+        assert_eq!(theme.name, "mine");
+        assert_eq!(theme.correct, Color::Rgb(0, 255, 0));
+        assert_eq!(theme.incorrect, Color::Magenta);
+        assert_eq!(theme.caret, Theme::default().caret);
+    }
+
+    #[test]
+    fn unknown_colour_points_at_its_line() {
+        let error =
+            Theme::from_toml("mine", "correct = \"green\"\ncaret = \"sparkly\"").unwrap_err();
+
+        assert!(error.to_string().starts_with("line 2:"));
+    }
+
+    #[test]
+    fn finding_an_unknown_theme_lists_the_known_ones() {
+        let error = Theme::find(&Theme::built_in(), "neon").unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "unknown theme 'neon', expected one of default, dracula, gruvbox, nord, serika"
+        );
+    }
+}
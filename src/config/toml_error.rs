@@ -0,0 +1,18 @@
+use std::{fmt::Display, ops::Range};
+
+use color_eyre::{eyre::eyre, Report};
+
+// an error for something in a toml file, saying which line of the file it's on
+pub fn line_error(text: &str, span: Range<usize>, message: impl Display) -> Report {
+    let line = text[..span.start.min(text.len())].matches('\n').count() + 1;
+    eyre!("line {}: {}", line, message)
+}
+
+// toml's own errors come with a snippet of the file, only the line and message are kept so they
+// read the same as the errors found after parsing
+pub fn toml_error(text: &str, error: toml::de::Error) -> Report {
+    match error.span() {
+        Some(span) => line_error(text, span, error.message().trim()),
+        None => eyre!("{}", error.message().trim()),
+    }
+}
@@ -0,0 +1,263 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::features::words_challenge::keystroke::{timed_presses, Keystroke};
+
+use super::pseudo_word_generator::PseudoWordGenerator;
+
+// The order letters are unlocked in, the most useful first so that early lessons can still make
+// plenty of words
+const LETTER_ORDER: [char; 26] = [
+    'e', 'n', 'i', 't', 'r', 'l', 's', 'a', 'u', 'o', 'd', 'y', 'c', 'h', 'g', 'm', 'p', 'b', 'k',
+    'v', 'w', 'f', 'z', 'x', 'q', 'j',
+];
+const STARTING_LETTERS: usize = 6;
+// each letter is judged on only its latest presses so that old slow typing doesn't hold it back
+const RECENT_PRESSES: usize = 20;
+// how fast and accurately every unlocked letter has to be typed before the next one is unlocked
+const TARGET_WPM: f32 = 35.0;
+const TARGET_ACCURACY: f32 = 95.0;
+
+// A single time a letter should have been typed
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct LetterPress {
+    correct: bool,
+    // how long a correct press took after the key pressed before it
+    latency: Option<Duration>,
+}
+
+// The letters being practised, with the weakest of them picked out to be practised the most
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdaptiveLetters {
+    pub unlocked: Vec<char>,
+    pub focus: char,
+}
+
+// How far through the letters adaptive practice has got. It's saved along with each adaptive
+// result, so deleting results from the history never takes it back
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AdaptiveProgress {
+    unlocked_count: usize,
+    recent_presses: BTreeMap<char, VecDeque<LetterPress>>,
+}
+
+impl Default for AdaptiveProgress {
+    fn default() -> Self {
+        Self {
+            unlocked_count: STARTING_LETTERS,
+            recent_presses: BTreeMap::new(),
+        }
+    }
+}
+
+impl AdaptiveProgress {
+    // the progress after each of the challenges in turn, oldest first, for results saved before
+    // the progress was. Letters are only ever unlocked at the end of a challenge and are never
+    // locked again
+    pub fn from_keystroke_logs(keystroke_logs: &[Vec<Keystroke>]) -> Self {
+        keystroke_logs
+            .iter()
+            .fold(Self::default(), |progress, keystrokes| {
+                progress.with_keystrokes(keystrokes)
+            })
+    }
+
+    // Adds a challenge's keystrokes to each letter's latest presses, timed the same way as the key
+    // stats, then unlocks the next letter if every one so far is good enough
+    pub fn with_keystrokes(mut self, keystrokes: &[Keystroke]) -> Self {
+        for press in timed_presses(keystrokes) {
+            if !LETTER_ORDER.contains(&press.expected) {
+                continue;
+            }
+
+            let presses = self.recent_presses.entry(press.expected).or_default();
+            presses.push_back(LetterPress {
+                correct: press.correct,
+                latency: press.latency,
+            });
+            if presses.len() > RECENT_PRESSES {
+                presses.pop_front();
+            }
+        }
+
+        if self.unlocked_count < LETTER_ORDER.len()
+            && self
+                .unlocked()
+                .iter()
+                .all(|letter| self.is_confident(*letter))
+        {
+            self.unlocked_count += 1;
+        }
+
+        self
+    }
+
+    pub fn unlocked(&self) -> &[char] {
+        &LETTER_ORDER[..self.unlocked_count]
+    }
+
+    // the unlocked letters with the one furthest from the targets as the focus, which is the
+    // newest letter straight after it's unlocked as it hasn't been typed yet
+    pub fn letters(&self) -> AdaptiveLetters {
+        let focus = self
+            .unlocked()
+            .iter()
+            .copied()
+            .min_by(|a, b| self.confidence(*a).total_cmp(&self.confidence(*b)))
+            .expect("some letters are always unlocked");
+
+        AdaptiveLetters {
+            unlocked: self.unlocked().to_vec(),
+            focus,
+        }
+    }
+
+    // made up words using only the unlocked letters, nearly all of them with the focus letter
+    pub fn lesson(&self, num_words: usize, seed: u64) -> String {
+        let letters = self.letters();
+        PseudoWordGenerator::new(&letters.unlocked, letters.focus, seed)
+            .words(num_words)
+            .join(" ")
+    }
+
+    // the wpm the letter is typed at going by its latest correct presses
+    fn letter_wpm(&self, letter: char) -> Option<f32> {
+        let latencies: Vec<Duration> = self
+            .recent_presses
+            .get(&letter)?
+            .iter()
+            .filter_map(|press| press.latency)
+            .collect();
+        if latencies.is_empty() {
+            return None;
+        }
+
+        let average = latencies.iter().sum::<Duration>() / latencies.len() as u32;
+        // a word is five characters, so this is how many words a minute at this speed
+        Some(60.0 / average.as_secs_f32().max(f32::EPSILON) / 5.0)
+    }
+
+    fn letter_accuracy(&self, letter: char) -> Option<f32> {
+        let presses = self.recent_presses.get(&letter)?;
+        let correct = presses.iter().filter(|press| press.correct).count();
+
+        Some(correct as f32 / presses.len() as f32 * 100.0)
+    }
+
+    // how close the letter is to the targets, the lower of its speed and accuracy against them
+    // where one means it's there. A letter that hasn't been typed right yet is nowhere
+    fn confidence(&self, letter: char) -> f32 {
+        match (self.letter_wpm(letter), self.letter_accuracy(letter)) {
+            (Some(wpm), Some(accuracy)) => (wpm / TARGET_WPM).min(accuracy / TARGET_ACCURACY),
+            _ => 0.0,
+        }
+    }
+
+    // letters have to have been typed enough times to be sure of them
+    fn is_confident(&self, letter: char) -> bool {
+        let press_count = self.recent_presses.get(&letter).map_or(0, VecDeque::len);
+        press_count >= RECENT_PRESSES && self.confidence(letter) >= 1.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::features::words_challenge::keystroke::KeystrokeKey;
+
+    use super::*;
+
+    // every unlocked letter typed the given number of times, each press the given gap apart
+    fn typing(
+        letters: &[char],
+        times: usize,
+        gap_millis: u64,
+        mistake: Option<char>,
+    ) -> Vec<Keystroke> {
+        letters
+            .iter()
+            .cycle()
+            .take(letters.len() * times)
+            .enumerate()
+            .map(|(index, expected)| Keystroke {
+                key: KeystrokeKey::Character(match mistake {
+                    // one in four presses of it are wrong
+                    Some(mistake)
+                        if mistake == *expected && (index / letters.len()).is_multiple_of(4) =>
+                    {
+                        'x'
+                    }
+                    _ => *expected,
+                }),
+                expected: Some(*expected),
+                position: index,
+                time: Duration::from_millis(index as u64 * gap_millis),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn practice_starts_with_a_few_letters() {
+        let progress = AdaptiveProgress::default();
+
+        assert_eq!(progress.unlocked(), &['e', 'n', 'i', 't', 'r', 'l']);
+    }
+
+    #[test]
+    fn fast_accurate_typing_unlocks_the_next_letter() {
+        let progress = AdaptiveProgress::default();
+        let keystrokes = typing(progress.unlocked(), RECENT_PRESSES, 200, None);
+
+        let progress = AdaptiveProgress::from_keystroke_logs(&[keystrokes]);
+
+        assert_eq!(progress.unlocked().len(), STARTING_LETTERS + 1);
+        assert_eq!(progress.letters().focus, 's');
+    }
+
+    #[test]
+    fn slow_typing_keeps_letters_locked() {
+        let progress = AdaptiveProgress::default();
+        // 500ms a letter is 24 wpm
+        let keystrokes = typing(progress.unlocked(), RECENT_PRESSES, 500, None);
+
+        let progress = AdaptiveProgress::from_keystroke_logs(&[keystrokes]);
+
+        assert_eq!(progress.unlocked().len(), STARTING_LETTERS);
+        assert_eq!(progress.letter_wpm('e'), Some(24.0));
+    }
+
+    #[test]
+    fn mistakes_on_one_letter_keep_letters_locked_and_make_it_the_focus() {
+        let progress = AdaptiveProgress::default();
+        let keystrokes = typing(progress.unlocked(), RECENT_PRESSES, 200, Some('t'));
+
+        let progress = AdaptiveProgress::from_keystroke_logs(&[keystrokes]);
+
+        assert_eq!(progress.unlocked().len(), STARTING_LETTERS);
+        assert_eq!(progress.letters().focus, 't');
+    }
+
+    #[test]
+    fn letters_are_only_unlocked_once_they_have_been_typed_enough() {
+        let progress = AdaptiveProgress::default();
+        let keystrokes = typing(progress.unlocked(), RECENT_PRESSES - 1, 200, None);
+
+        let progress = AdaptiveProgress::from_keystroke_logs(&[keystrokes]);
+
+        assert_eq!(progress.unlocked().len(), STARTING_LETTERS);
+    }
+
+    #[test]
+    fn lessons_only_use_unlocked_letters() {
+        let progress = AdaptiveProgress::default();
+        let lesson = progress.lesson(20, 9);
+
+        assert_eq!(lesson.split(' ').count(), 20);
+        assert!(lesson
+            .chars()
+            .all(|letter| letter == ' ' || progress.unlocked().contains(&letter)));
+    }
+}
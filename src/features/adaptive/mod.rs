@@ -0,0 +1,2 @@
+pub(crate) mod adaptive_progress;
+pub(crate) mod pseudo_word_generator;
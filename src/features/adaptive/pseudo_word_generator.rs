@@ -0,0 +1,231 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::OnceLock,
+};
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::features::words_challenge::word_generator::WordBand;
+
+const MIN_WORD_LENGTH: usize = 3;
+const MAX_WORD_LENGTH: usize = 8;
+// how many words are tried for one that has the focus letter in it before settling for one that
+// doesn't, a few letters can't always make a word with it
+const FOCUS_ATTEMPTS: usize = 50;
+// how much more likely the focus letter is to be picked than the word list alone would make it
+const FOCUS_BOOST: u32 = 3;
+// stand in for the start and end of a word in the letter sequences
+const WORD_START: char = '^';
+const WORD_END: char = '$';
+
+// How often each letter follows the one or two before it across the english word list, which is
+// enough to make up words that read like english without being real ones
+struct LetterSequences {
+    after_two: HashMap<(char, char), BTreeMap<char, u32>>,
+    after_one: HashMap<char, BTreeMap<char, u32>>,
+}
+
+fn letter_sequences() -> &'static LetterSequences {
+    static SEQUENCES: OnceLock<LetterSequences> = OnceLock::new();
+    SEQUENCES.get_or_init(|| {
+        let mut sequences = LetterSequences {
+            after_two: HashMap::new(),
+            after_one: HashMap::new(),
+        };
+
+        for word in WordBand::Top10k.words() {
+            let letters: Vec<char> = [WORD_START, WORD_START]
+                .into_iter()
+                .chain(word.chars())
+                .chain([WORD_END])
+                .collect();
+
+            for window in letters.windows(3) {
+                *sequences
+                    .after_two
+                    .entry((window[0], window[1]))
+                    .or_default()
+                    .entry(window[2])
+                    .or_default() += 1;
+                *sequences
+                    .after_one
+                    .entry(window[1])
+                    .or_default()
+                    .entry(window[2])
+                    .or_default() += 1;
+            }
+        }
+
+        sequences
+    })
+}
+
+// Makes up words from only the given letters, with the focus letter in as many of them as
+// possible. Like the word generator the rng is ChaCha so the same seed gives the same words
+#[derive(Debug, Clone)]
+pub struct PseudoWordGenerator {
+    letters: Vec<char>,
+    focus: char,
+    rng: ChaCha8Rng,
+    previous_word: Option<String>,
+}
+
+impl PseudoWordGenerator {
+    pub fn new(letters: &[char], focus: char, seed: u64) -> Self {
+        Self {
+            letters: letters.to_vec(),
+            focus,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+            previous_word: None,
+        }
+    }
+
+    // makes up the next word, never giving the same word twice in a row
+    pub fn next_word(&mut self) -> String {
+        let mut without_focus = None;
+
+        for _ in 0..FOCUS_ATTEMPTS {
+            let Some(word) = self.try_word() else {
+                continue;
+            };
+            if self.previous_word.as_ref() == Some(&word) {
+                continue;
+            }
+            if word.contains(self.focus) {
+                return self.use_word(word);
+            }
+            without_focus.get_or_insert(word);
+        }
+
+        // letters that never follow each other in english are put together at random instead
+        let word = without_focus.unwrap_or_else(|| self.random_letters());
+        self.use_word(word)
+    }
+
+    pub fn words(&mut self, count: usize) -> Vec<String> {
+        (0..count).map(|_| self.next_word()).collect()
+    }
+
+    fn use_word(&mut self, word: String) -> String {
+        self.previous_word = Some(word.clone());
+        word
+    }
+
+    // follows the letter sequences from the start of a word until it ends, giving up if the
+    // letters it's allowed run out before then
+    fn try_word(&mut self) -> Option<String> {
+        let mut word = String::new();
+        let mut previous = (WORD_START, WORD_START);
+
+        loop {
+            let length = word.chars().count();
+            let next_letters = self.next_letters(previous, length);
+            let total: u32 = next_letters.iter().map(|(_, weight)| weight).sum();
+            if total == 0 {
+                return None;
+            }
+
+            let mut pick = self.rng.gen_range(0..total);
+            let (next, _) = next_letters
+                .into_iter()
+                .find(|(_, weight)| match pick.checked_sub(*weight) {
+                    Some(rest) => {
+                        pick = rest;
+                        false
+                    }
+                    None => true,
+                })
+                .expect("pick should be less than the total weight");
+
+            if next == WORD_END {
+                return Some(word);
+            }
+            word.push(next);
+            previous = (previous.1, next);
+        }
+    }
+
+    // the letters that can come next with how likely each is, going by the two letters before
+    // where english has them next to each other and the one before where it doesn't
+    fn next_letters(&self, previous: (char, char), length: usize) -> Vec<(char, u32)> {
+        let sequences = letter_sequences();
+        let allowed = |next: &char| match *next {
+            WORD_END => length >= MIN_WORD_LENGTH,
+            letter => length < MAX_WORD_LENGTH && self.letters.contains(&letter),
+        };
+        let weighted = |(next, count): (&char, &u32)| {
+            let boost = if *next == self.focus { FOCUS_BOOST } else { 1 };
+            (*next, count * boost)
+        };
+
+        let after_two: Vec<(char, u32)> = sequences
+            .after_two
+            .get(&previous)
+            .into_iter()
+            .flatten()
+            .filter(|(next, _)| allowed(next))
+            .map(weighted)
+            .collect();
+        if !after_two.is_empty() {
+            return after_two;
+        }
+
+        sequences
+            .after_one
+            .get(&previous.1)
+            .into_iter()
+            .flatten()
+            .filter(|(next, _)| allowed(next))
+            .map(weighted)
+            .collect()
+    }
+
+    fn random_letters(&mut self) -> String {
+        let length = self.rng.gen_range(MIN_WORD_LENGTH..=MAX_WORD_LENGTH);
+        (0..length)
+            .map(|_| self.letters[self.rng.gen_range(0..self.letters.len())])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LETTERS: [char; 6] = ['e', 'n', 'i', 't', 'r', 'l'];
+
+    #[test]
+    fn words_only_use_the_given_letters() {
+        let words = PseudoWordGenerator::new(&LETTERS, 'r', 7).words(200);
+
+        assert!(words
+            .iter()
+            .all(|word| word.chars().all(|letter| LETTERS.contains(&letter))));
+        assert!(words
+            .iter()
+            .all(|word| (MIN_WORD_LENGTH..=MAX_WORD_LENGTH).contains(&word.chars().count())));
+    }
+
+    #[test]
+    fn words_have_the_focus_letter_in_them() {
+        let words = PseudoWordGenerator::new(&LETTERS, 'l', 3).words(200);
+
+        assert!(words.iter().all(|word| word.contains('l')));
+    }
+
+    #[test]
+    fn same_seed_makes_up_same_words() {
+        let first = PseudoWordGenerator::new(&LETTERS, 'e', 42).words(20);
+        let second = PseudoWordGenerator::new(&LETTERS, 'e', 42).words(20);
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn same_word_never_appears_twice_in_a_row() {
+        let words = PseudoWordGenerator::new(&LETTERS, 'n', 1).words(500);
+
+        assert!(words.windows(2).all(|pair| pair[0] != pair[1]));
+    }
+}
@@ -0,0 +1,301 @@
+use color_eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{config::keybindings::Keybindings, tui::message::Message};
+
+use super::{history_query::HistoryQuery, result_summary::ResultSummary};
+
+// how many rows page up and page down move the selection by
+const PAGE_SIZE: usize = 10;
+
+#[derive(Debug)]
+pub struct HistoryModel {
+    pub(super) query: HistoryQuery,
+    // the languages that can be filtered by
+    pub(super) languages: Vec<String>,
+    // the results matching the query, in the order they are listed
+    pub(super) rows: Vec<ResultSummary>,
+    // index into rows
+    pub(super) selected: usize,
+    // delete has been pressed once, pressing it again deletes the selected result
+    pub(super) confirming_delete: bool,
+}
+
+impl HistoryModel {
+    pub fn new(query: HistoryQuery, languages: Vec<String>, rows: Vec<ResultSummary>) -> Self {
+        Self {
+            query,
+            languages,
+            rows,
+            selected: 0,
+            confirming_delete: false,
+        }
+    }
+
+    pub(super) fn selected_row(&self) -> Option<&ResultSummary> {
+        self.rows.get(self.selected)
+    }
+
+    // swaps in the results for a changed query, going back to the top of the list
+    pub(super) fn with_rows(self, rows: Vec<ResultSummary>) -> Self {
+        Self {
+            rows,
+            selected: 0,
+            ..self
+        }
+    }
+
+    // drops the selected result after it has been deleted, keeping the selection in place
+    pub(super) fn without_selected_row(self) -> Self {
+        let mut rows = self.rows;
+        if self.selected < rows.len() {
+            rows.remove(self.selected);
+        }
+
+        Self {
+            selected: self.selected.min(rows.len().saturating_sub(1)),
+            rows,
+            confirming_delete: false,
+            ..self
+        }
+    }
+
+    pub fn handle_event(
+        &self,
+        key_event: KeyEvent,
+        keybindings: &Keybindings,
+    ) -> Result<Option<Message>> {
+        let message = match key_event.code {
+            key if key == keybindings.race => {
+                self.selected_row().map(|row| Message::StartRace(row.id))
+            }
+            key if key == keybindings.menu => Some(Message::OpenMenu),
+            keycode => Some(Message::HistoryInput(keycode)),
+        };
+
+        Ok(message)
+    }
+
+    pub(super) fn handle_history_input(self, keycode: KeyCode) -> Self {
+        let last_row = self.rows.len().saturating_sub(1);
+        // any other key cancels a delete
+        let history = Self {
+            confirming_delete: false,
+            ..self
+        };
+
+        match keycode {
+            KeyCode::Down | KeyCode::Char('j') => Self {
+                selected: (history.selected + 1).min(last_row),
+                ..history
+            },
+            KeyCode::Up | KeyCode::Char('k') => Self {
+                selected: history.selected.saturating_sub(1),
+                ..history
+            },
+            KeyCode::PageDown => Self {
+                selected: (history.selected + PAGE_SIZE).min(last_row),
+                ..history
+            },
+            KeyCode::PageUp => Self {
+                selected: history.selected.saturating_sub(PAGE_SIZE),
+                ..history
+            },
+            KeyCode::Home | KeyCode::Char('g') => Self { selected: 0, ..history },
+            KeyCode::End | KeyCode::Char('G') => Self {
+                selected: last_row,
+                ..history
+            },
+            KeyCode::Char('d') => Self {
+                confirming_delete: history.selected_row().is_some(),
+                ..history
+            },
+            KeyCode::Char('s') => Self {
+                query: HistoryQuery {
+                    sort: history.query.sort.next(),
+                    ..history.query
+                },
+                ..history
+            },
+            KeyCode::Char('f') => Self {
+                query: HistoryQuery {
+                    mode: history.query.mode.next(),
+                    ..history.query
+                },
+                ..history
+            },
+            KeyCode::Char('l') => {
+                let language = history.next_language();
+                Self {
+                    query: HistoryQuery {
+                        language,
+                        ..history.query
+                    },
+                    ..history
+                }
+            }
+            _ => history,
+        }
+    }
+
+    // cycles from every language through each one on its own, and then back to every language
+    fn next_language(&self) -> Option<String> {
+        let next_index = match &self.query.language {
+            None => 0,
+            Some(language) => match self.languages.iter().position(|known| known == language) {
+                Some(index) => index + 1,
+                None => 0,
+            },
+        };
+
+        self.languages.get(next_index).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chrono::Local;
+
+    use crate::features::{
+        history::history_query::{HistorySort, ModeFilter},
+        words_challenge::{
+            challenge_mode::ChallengeMode, challenge_settings::ChallengeSettings,
+            word_generator::WordBand,
+        },
+    };
+
+    use super::*;
+
+    fn model_with_rows(count: usize) -> HistoryModel {
+        let rows = (0..count)
+            .map(|id| ResultSummary {
+                id: id as i64,
+                settings: ChallengeSettings {
+                    mode: ChallengeMode::Words(25),
+                    word_band: WordBand::Top200,
+                },
+                language: "english".to_string(),
+                wpm: 60.0,
+                accuracy: 100.0,
+                duration: Duration::from_secs(20),
+                completed_at: Local::now(),
+                failed: false,
+            })
+            .collect();
+
+        HistoryModel::new(HistoryQuery::default(), vec!["english".to_string()], rows)
+    }
+
+    #[test]
+    fn moving_down_stops_at_last_row() {
+        let model = model_with_rows(2)
+            .handle_history_input(KeyCode::Down)
+            .handle_history_input(KeyCode::Down);
+
+        assert_eq!(model.selected, 1);
+    }
+
+    #[test]
+    fn moving_up_stops_at_first_row() {
+        let model = model_with_rows(2).handle_history_input(KeyCode::Up);
+        assert_eq!(model.selected, 0);
+    }
+
+    #[test]
+    fn moving_in_empty_history_selects_nothing() {
+        let model = model_with_rows(0).handle_history_input(KeyCode::End);
+
+        assert_eq!(model.selected, 0);
+        assert!(model.selected_row().is_none());
+    }
+
+    #[test]
+    fn page_down_moves_a_page_of_rows() {
+        let model = model_with_rows(50).handle_history_input(KeyCode::PageDown);
+        assert_eq!(model.selected, PAGE_SIZE);
+    }
+
+    #[test]
+    fn sort_and_mode_keys_change_query() {
+        let model = model_with_rows(1)
+            .handle_history_input(KeyCode::Char('s'))
+            .handle_history_input(KeyCode::Char('f'));
+
+        assert_eq!(model.query.sort, HistorySort::Wpm);
+        assert_eq!(model.query.mode, ModeFilter::Words(None));
+    }
+
+    #[test]
+    fn language_cycles_through_languages_then_back_to_all() {
+        let model = model_with_rows(1).handle_history_input(KeyCode::Char('l'));
+        assert_eq!(model.query.language, Some("english".to_string()));
+
+        let model = model.handle_history_input(KeyCode::Char('l'));
+        assert_eq!(model.query.language, None);
+    }
+
+    #[test]
+    fn new_rows_reset_selection() {
+        let model = model_with_rows(5).handle_history_input(KeyCode::End);
+        let model = model.with_rows(Vec::new());
+
+        assert_eq!(model.selected, 0);
+    }
+
+    #[test]
+    fn delete_needs_confirming() {
+        let model = model_with_rows(2).handle_history_input(KeyCode::Char('d'));
+        assert!(model.confirming_delete);
+
+        let model = model.handle_history_input(KeyCode::Down);
+        assert!(!model.confirming_delete);
+    }
+
+    #[test]
+    fn g_goes_to_the_top_and_race_key_races_the_selected_result() {
+        let keybindings = Keybindings::default();
+        let model = model_with_rows(3).handle_history_input(KeyCode::End);
+
+        assert!(matches!(
+            model.handle_event(KeyEvent::from(KeyCode::Char('g')), &keybindings).unwrap(),
+            Some(Message::HistoryInput(KeyCode::Char('g')))
+        ));
+        assert_eq!(model.handle_history_input(KeyCode::Char('g')).selected, 0);
+
+        let model = model_with_rows(3).handle_history_input(KeyCode::Down);
+        assert!(matches!(
+            model.handle_event(KeyEvent::from(keybindings.race), &keybindings).unwrap(),
+            Some(Message::StartRace(1))
+        ));
+    }
+
+    #[test]
+    fn configured_menu_key_opens_the_menu() {
+        let keybindings = Keybindings {
+            menu: KeyCode::F(1),
+            ..Keybindings::default()
+        };
+        let model = model_with_rows(1);
+
+        assert!(matches!(
+            model.handle_event(KeyEvent::from(KeyCode::F(1)), &keybindings).unwrap(),
+            Some(Message::OpenMenu)
+        ));
+        assert!(matches!(
+            model.handle_event(KeyEvent::from(KeyCode::Char('m')), &keybindings).unwrap(),
+            Some(Message::HistoryInput(KeyCode::Char('m')))
+        ));
+    }
+
+    #[test]
+    fn removing_last_row_selects_new_last_row() {
+        let model = model_with_rows(3)
+            .handle_history_input(KeyCode::End)
+            .without_selected_row();
+
+        assert_eq!(model.rows.len(), 2);
+        assert_eq!(model.selected, 1);
+    }
+}
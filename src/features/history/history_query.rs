@@ -0,0 +1,140 @@
+use std::{fmt, str::FromStr};
+
+use crate::features::words_challenge::{challenge_mode::ChallengeMode, quote::QuoteLength};
+
+// Which saved results the history shows and in what order
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HistoryQuery {
+    pub sort: HistorySort,
+    pub mode: ModeFilter,
+    // results in any language are shown when there isn't one
+    pub language: Option<String>,
+    // only this many of the first results in order, or all of them when there isn't one
+    pub limit: Option<usize>,
+}
+
+// Every sort puts the newest, fastest or most accurate results first
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HistorySort {
+    #[default]
+    Date,
+    Wpm,
+    Accuracy,
+}
+
+impl HistorySort {
+    pub const ALL: [HistorySort; 3] = [HistorySort::Date, HistorySort::Wpm, HistorySort::Accuracy];
+
+    pub fn next(&self) -> Self {
+        match self {
+            HistorySort::Date => HistorySort::Wpm,
+            HistorySort::Wpm => HistorySort::Accuracy,
+            HistorySort::Accuracy => HistorySort::Date,
+        }
+    }
+}
+
+impl fmt::Display for HistorySort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HistorySort::Date => write!(f, "date"),
+            HistorySort::Wpm => write!(f, "wpm"),
+            HistorySort::Accuracy => write!(f, "accuracy"),
+        }
+    }
+}
+
+impl FromStr for HistorySort {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|sort| sort.to_string() == name)
+            .ok_or_else(|| format!("unknown sort '{}'", name))
+    }
+}
+
+// Narrows the history to one mode, and optionally one word count, time limit or quote length of
+// it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ModeFilter {
+    #[default]
+    All,
+    Words(Option<usize>),
+    Time(Option<u64>),
+    Quote(Option<QuoteLength>),
+    // custom text and adaptive lessons have no set lengths to narrow them down by
+    Custom,
+    Adaptive,
+}
+
+impl ModeFilter {
+    // every filter in the order they are cycled through
+    pub fn all() -> Vec<ModeFilter> {
+        let word_counts = ChallengeMode::WORD_COUNTS.map(|count| ModeFilter::Words(Some(count)));
+        let time_limits = ChallengeMode::TIME_LIMITS.map(|limit| ModeFilter::Time(Some(limit)));
+        let quote_lengths = QuoteLength::ALL.map(|length| ModeFilter::Quote(Some(length)));
+
+        [ModeFilter::All, ModeFilter::Words(None)]
+            .into_iter()
+            .chain(word_counts)
+            .chain([ModeFilter::Time(None)])
+            .chain(time_limits)
+            .chain([ModeFilter::Quote(None)])
+            .chain(quote_lengths)
+            .chain([ModeFilter::Custom, ModeFilter::Adaptive])
+            .collect()
+    }
+
+    pub fn next(&self) -> Self {
+        let filters = Self::all();
+        let index = filters.iter().position(|filter| filter == self).unwrap_or(0);
+        filters[(index + 1) % filters.len()]
+    }
+}
+
+impl fmt::Display for ModeFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModeFilter::All => write!(f, "all"),
+            ModeFilter::Words(None) => write!(f, "words"),
+            ModeFilter::Words(Some(count)) => write!(f, "words {}", count),
+            ModeFilter::Time(None) => write!(f, "time"),
+            ModeFilter::Time(Some(limit)) => write!(f, "time {}", limit),
+            ModeFilter::Quote(None) => write!(f, "quote"),
+            ModeFilter::Quote(Some(length)) => write!(f, "quote {}", length),
+            ModeFilter::Custom => write!(f, "custom"),
+            ModeFilter::Adaptive => write!(f, "adaptive"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mode_filter_cycles_through_every_length() {
+        let mut filter = ModeFilter::All;
+        let mut seen = vec![filter];
+
+        loop {
+            filter = filter.next();
+            if filter == ModeFilter::All {
+                break;
+            }
+            seen.push(filter);
+        }
+
+        assert_eq!(seen, ModeFilter::all());
+        assert!(seen.contains(&ModeFilter::Words(Some(50))));
+        assert!(seen.contains(&ModeFilter::Time(Some(60))));
+    }
+
+    #[test]
+    fn sort_cycles_back_to_date() {
+        let sort = HistorySort::Date.next().next().next();
+        assert_eq!(sort, HistorySort::Date);
+    }
+}
@@ -0,0 +1,27 @@
+use std::io::{self, Write};
+
+use super::result_summary::ResultSummary;
+
+// Prints results as a plain text table for `typee history`, with the same columns as the history
+// page
+pub fn write_history(rows: &[ResultSummary], out: &mut impl Write) -> io::Result<()> {
+    if rows.is_empty() {
+        return writeln!(out, "no results yet");
+    }
+
+    writeln!(out, "{:<18}{:<24}{:>7}{:>8}{:>8}", "date", "test", "wpm", "acc", "time")?;
+    for row in rows {
+        writeln!(
+            out,
+            "{:<18}{:<24}{:>7.1}{:>7.1}%{:>7.1}s{}",
+            row.completed_at.format("%Y-%m-%d %H:%M").to_string(),
+            row.settings.to_string(),
+            row.wpm,
+            row.accuracy,
+            row.duration.as_secs_f32(),
+            if row.failed { "  failed" } else { "" }
+        )?;
+    }
+
+    Ok(())
+}
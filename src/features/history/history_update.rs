@@ -0,0 +1,77 @@
+use crossterm::event::KeyCode;
+
+use crate::{
+    features::results::results_model::ResultsModel,
+    tui::{app_model::AppModel, app_page::AppPage, message::Message},
+};
+use color_eyre::Result;
+
+pub fn history_update(model: AppModel, message: Message) -> Result<(AppModel, Option<Message>)> {
+    let history = match model.app_page {
+        AppPage::History(history) => history,
+        _ => panic!(
+            "history cannot use non history models, found: {:?}",
+            model.app_page
+        ),
+    };
+
+    match message {
+        Message::HistoryInput(KeyCode::Enter) => {
+            let page = match history.selected_row() {
+                Some(row) => {
+                    let result = model.database.load_result(row.id)?;
+                    let previous_best =
+                        model.database.personal_best_before(&result, row.id)?;
+                    AppPage::Results(ResultsModel::new(result, previous_best))
+                }
+                None => AppPage::History(history),
+            };
+
+            Ok((
+                AppModel {
+                    app_page: page,
+                    ..model
+                },
+                None,
+            ))
+        }
+        Message::HistoryInput(KeyCode::Char('d')) if history.confirming_delete => {
+            if let Some(row) = history.selected_row() {
+                model.database.delete_result(row.id)?;
+            }
+
+            Ok((
+                AppModel {
+                    app_page: AppPage::History(history.without_selected_row()),
+                    ..model
+                },
+                None,
+            ))
+        }
+        Message::HistoryInput(keycode) => {
+            let previous_query = history.query.clone();
+            let mut new_history = history.handle_history_input(keycode);
+
+            // the list only needs loading again when it would show different results
+            if new_history.query != previous_query {
+                let rows = model.database.load_result_summaries(&new_history.query)?;
+                new_history = new_history.with_rows(rows);
+            }
+
+            Ok((
+                AppModel {
+                    app_page: AppPage::History(new_history),
+                    ..model
+                },
+                None,
+            ))
+        }
+        _ => Ok((
+            AppModel {
+                app_page: AppPage::History(history),
+                ..model
+            },
+            None,
+        )),
+    }
+}
@@ -0,0 +1,98 @@
+use ratatui::layout::Flex;
+use ratatui::prelude::*;
+use ratatui::widgets::{Cell, Paragraph, Row, Table, TableState};
+use ratatui::Frame;
+
+use crate::config::{app_config::AppConfig, keybindings::key_name};
+
+use super::history_model::HistoryModel;
+
+// Lists past results in a table filling the middle of the screen, with the current sort and
+// filters above it
+pub fn history_view(history: &HistoryModel, config: &AppConfig, frame: &mut Frame) {
+    let horizontal_center = Layout::horizontal([Constraint::Length(80)]).flex(Flex::Center).split(frame.area())[0];
+    let [title_area, query_area, _, table_area, _, help_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Fill(1),
+        Constraint::Length(1),
+        Constraint::Length(1),
+    ])
+    .areas(horizontal_center.inner(Margin::new(0, 1)));
+
+    let theme = &config.theme;
+    let highlight = Style::new().fg(theme.accent);
+    let label = Style::new().fg(theme.label);
+
+    let title = Paragraph::new("history").style(highlight.bold());
+
+    let query = &history.query;
+    let query_line = Line::from(vec![
+        Span::styled("sort ", label),
+        Span::raw(query.sort.to_string()),
+        Span::styled("   mode ", label),
+        Span::raw(query.mode.to_string()),
+        Span::styled("   language ", label),
+        Span::raw(query.language.as_deref().unwrap_or("all")),
+        Span::styled(format!("   {} results", history.rows.len()), label),
+    ]);
+
+    let help = if history.confirming_delete {
+        Paragraph::new("press d again to delete this result").style(Style::new().fg(theme.warning))
+    } else {
+        Paragraph::new(format!(
+            "↑/↓ select · enter open · {} race · d delete · s sort · f mode · l lang · {} menu",
+            key_name(config.keybindings.race),
+            key_name(config.keybindings.menu)
+        ))
+        .style(label)
+    };
+
+    frame.render_widget(title, title_area);
+    frame.render_widget(Paragraph::new(query_line), query_area);
+    frame.render_widget(help, help_area);
+
+    if history.rows.is_empty() {
+        frame.render_widget(Paragraph::new("no results yet").style(label), table_area);
+        return;
+    }
+
+    // only the rows that fit are built, so a long history doesn't slow down every frame
+    let visible_rows = table_area.height.saturating_sub(1).max(1) as usize;
+    let offset = history
+        .selected
+        .saturating_sub(visible_rows / 2)
+        .min(history.rows.len().saturating_sub(visible_rows));
+
+    let failed = Cell::from("failed").style(Style::new().fg(theme.warning));
+    let rows = history.rows.iter().skip(offset).take(visible_rows).map(|row| {
+        Row::new(vec![
+            Cell::from(row.completed_at.format("%Y-%m-%d %H:%M").to_string()),
+            Cell::from(row.settings.to_string()),
+            Cell::from(format!("{:.1}", row.wpm)),
+            Cell::from(format!("{:.1}%", row.accuracy)),
+            Cell::from(format!("{:.1}s", row.duration.as_secs_f32())),
+            if row.failed { failed.clone() } else { Cell::default() },
+        ])
+    });
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(16),
+            Constraint::Length(22),
+            Constraint::Length(7),
+            Constraint::Length(7),
+            Constraint::Length(7),
+            Constraint::Length(6),
+        ],
+    )
+    .header(Row::new(vec!["date", "test", "wpm", "acc", "time"]).style(label))
+    .style(Style::new().fg(theme.secondary))
+    .highlight_style(highlight)
+    .highlight_symbol("> ");
+    let mut table_state = TableState::default().with_selected(Some(history.selected - offset));
+
+    frame.render_stateful_widget(table, table_area, &mut table_state);
+}
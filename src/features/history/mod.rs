@@ -0,0 +1,6 @@
+pub(crate) mod history_model;
+pub(crate) mod history_query;
+pub(crate) mod history_report;
+pub(crate) mod history_update;
+pub(crate) mod history_view;
+pub(crate) mod result_summary;
@@ -0,0 +1,19 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+
+use crate::features::words_challenge::challenge_settings::ChallengeSettings;
+
+// A saved result as it is listed in the history, without the samples needed to chart it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResultSummary {
+    pub id: i64,
+    pub settings: ChallengeSettings,
+    pub language: String,
+    pub wpm: f32,
+    pub accuracy: f32,
+    pub duration: Duration,
+    pub completed_at: DateTime<Local>,
+    // failed results are shown but never count as a personal best
+    pub failed: bool,
+}
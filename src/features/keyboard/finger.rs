@@ -0,0 +1,73 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hand {
+    Left,
+    Right,
+}
+
+impl Hand {
+    pub fn other(self) -> Self {
+        match self {
+            Hand::Left => Hand::Right,
+            Hand::Right => Hand::Left,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Finger {
+    Pinky,
+    Ring,
+    Middle,
+    Index,
+    Thumb,
+}
+
+// the finger for each column of the letter rows when touch typing, anything further right than
+// these is reached with the right pinky
+const COLUMN_FINGERS: [(Hand, Finger); 10] = [
+    (Hand::Left, Finger::Pinky),
+    (Hand::Left, Finger::Ring),
+    (Hand::Left, Finger::Middle),
+    (Hand::Left, Finger::Index),
+    (Hand::Left, Finger::Index),
+    (Hand::Right, Finger::Index),
+    (Hand::Right, Finger::Index),
+    (Hand::Right, Finger::Middle),
+    (Hand::Right, Finger::Ring),
+    (Hand::Right, Finger::Pinky),
+];
+
+// The hand and finger that press the key at the given row and column. The number row sits half a
+// key further left than the row below, so each finger reaches one column further along it
+pub fn finger_for(row: usize, column: usize) -> (Hand, Finger) {
+    let column = if row == 0 {
+        column.saturating_sub(1)
+    } else {
+        column
+    };
+
+    COLUMN_FINGERS
+        .get(column)
+        .copied()
+        .unwrap_or((Hand::Right, Finger::Pinky))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn home_row_keys_use_their_fingers() {
+        assert_eq!(finger_for(2, 0), (Hand::Left, Finger::Pinky));
+        assert_eq!(finger_for(2, 4), (Hand::Left, Finger::Index));
+        assert_eq!(finger_for(2, 5), (Hand::Right, Finger::Index));
+        assert_eq!(finger_for(2, 10), (Hand::Right, Finger::Pinky));
+    }
+
+    #[test]
+    fn number_row_is_one_column_further_along() {
+        assert_eq!(finger_for(0, 1), (Hand::Left, Finger::Pinky));
+        assert_eq!(finger_for(0, 2), (Hand::Left, Finger::Ring));
+        assert_eq!(finger_for(0, 10), (Hand::Right, Finger::Pinky));
+    }
+}
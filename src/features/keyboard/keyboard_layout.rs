@@ -0,0 +1,386 @@
+use std::{fs, path::Path};
+
+use color_eyre::{
+    eyre::{eyre, WrapErr},
+    Result,
+};
+use crossterm::event::KeyCode;
+use serde::Deserialize;
+use toml::Spanned;
+
+use crate::config::toml_error::{line_error, toml_error};
+
+use super::finger::{finger_for, Finger, Hand};
+
+// The character each key types without shift and with it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Key {
+    pub base: char,
+    pub shifted: char,
+}
+
+impl Key {
+    pub fn types(&self, char: char) -> bool {
+        self.base == char || self.shifted == char
+    }
+
+    // how the key is labelled when it's listed
+    pub fn name(&self) -> String {
+        match self.base {
+            ' ' => "space".to_string(),
+            base => base.to_string(),
+        }
+    }
+}
+
+// A key drawn on a keyboard, the typing keys are kept with where they are in the layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyboardKey {
+    Typing { key: Key, row: usize, column: usize },
+    Space,
+    Shift(Hand),
+}
+
+impl KeyboardKey {
+    // the space bar can be pressed by either thumb
+    pub fn hand(&self) -> Option<Hand> {
+        match self {
+            KeyboardKey::Typing { row, column, .. } => Some(finger_for(*row, *column).0),
+            KeyboardKey::Space => None,
+            KeyboardKey::Shift(hand) => Some(*hand),
+        }
+    }
+
+    pub fn finger(&self) -> Finger {
+        match self {
+            KeyboardKey::Typing { row, column, .. } => finger_for(*row, *column).1,
+            KeyboardKey::Space => Finger::Thumb,
+            KeyboardKey::Shift(_) => Finger::Pinky,
+        }
+    }
+}
+
+// Layouts that come with the app, written the same way as a user's own layout files
+const BUILT_IN_LAYOUTS: [(&str, &str); 3] = [
+    ("colemak", include_str!("layouts/colemak.toml")),
+    ("dvorak", include_str!("layouts/dvorak.toml")),
+    ("workman", include_str!("layouts/workman.toml")),
+];
+
+const DEFAULT_LAYOUT_NAME: &str = "qwerty";
+
+// the layout the keys pressed are read in, each row is the characters typed without shift and
+// then the ones typed with it
+const QWERTY_ROWS: [(&str, &str); 4] = [
+    ("`1234567890-=", "~!@#$%^&*()_+"),
+    ("qwertyuiop[]\\", "QWERTYUIOP{}|"),
+    ("asdfghjkl;'", "ASDFGHJKL:\""),
+    ("zxcvbnm,./", "ZXCVBNM<>?"),
+];
+const ROW_NAMES: [&str; 4] = ["number", "top", "home", "bottom"];
+
+// The typing keys of a keyboard, row by row from the number row down. The space bar is left out
+// as it's the same on every layout
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyboardLayout {
+    // taken from the file name rather than written in the file
+    pub name: String,
+    rows: Vec<Vec<Key>>,
+}
+
+// A layout file as it's written, each row has to have as many keys as the same row on a qwerty
+// keyboard so that every key pressed has somewhere to go
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LayoutToml {
+    number: Spanned<(String, String)>,
+    top: Spanned<(String, String)>,
+    home: Spanned<(String, String)>,
+    bottom: Spanned<(String, String)>,
+}
+
+impl Default for KeyboardLayout {
+    fn default() -> Self {
+        Self::qwerty()
+    }
+}
+
+impl KeyboardLayout {
+    pub fn qwerty() -> Self {
+        Self::from_rows(DEFAULT_LAYOUT_NAME, &QWERTY_ROWS)
+    }
+
+    fn from_rows(name: &str, rows: &[(&str, &str)]) -> Self {
+        let rows = rows
+            .iter()
+            .map(|(base, shifted)| {
+                base.chars()
+                    .zip(shifted.chars())
+                    .map(|(base, shifted)| Key { base, shifted })
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            name: name.to_string(),
+            rows,
+        }
+    }
+
+    pub fn from_toml(name: &str, text: &str) -> Result<Self> {
+        let layout: LayoutToml = toml::from_str(text).map_err(|error| toml_error(text, error))?;
+
+        let rows = [layout.number, layout.top, layout.home, layout.bottom];
+        for ((row, qwerty_row), row_name) in rows.iter().zip(QWERTY_ROWS).zip(ROW_NAMES) {
+            let (base, shifted) = row.get_ref();
+            let key_count = qwerty_row.0.chars().count();
+            if base.chars().count() != key_count || shifted.chars().count() != key_count {
+                return Err(line_error(
+                    text,
+                    row.span(),
+                    format!(
+                        "the {} row should have {} keys with and without shift",
+                        row_name, key_count
+                    ),
+                ));
+            }
+        }
+
+        let rows: Vec<(&str, &str)> = rows
+            .iter()
+            .map(|row| (row.get_ref().0.as_str(), row.get_ref().1.as_str()))
+            .collect();
+        Ok(Self::from_rows(name, &rows))
+    }
+
+    // qwerty and the others that come with the app
+    pub fn built_in() -> Vec<KeyboardLayout> {
+        let bundled_layouts = BUILT_IN_LAYOUTS.iter().map(|(name, text)| {
+            Self::from_toml(name, text).expect("built in layouts should be valid")
+        });
+
+        [Self::qwerty()]
+            .into_iter()
+            .chain(bundled_layouts)
+            .collect()
+    }
+
+    // Every built in layout and then each .toml file in the given directory, named after the
+    // file. A user's layout with the same name as a built in one replaces it
+    pub fn load_all(layouts_directory: Option<&Path>) -> Result<Vec<KeyboardLayout>> {
+        let mut layouts = Self::built_in();
+
+        let Some(layouts_directory) = layouts_directory.filter(|directory| directory.is_dir())
+        else {
+            return Ok(layouts);
+        };
+
+        let mut paths: Vec<_> = fs::read_dir(layouts_directory)
+            .wrap_err_with(|| {
+                format!(
+                    "failed to read layouts from {}",
+                    layouts_directory.display()
+                )
+            })?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension == "toml")
+            })
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            let name = path.file_stem().unwrap_or_default().to_string_lossy();
+            let text = fs::read_to_string(&path)
+                .wrap_err_with(|| format!("failed to read layout file {}", path.display()))?;
+            let layout = Self::from_toml(&name, &text)
+                .wrap_err_with(|| format!("invalid layout file {}", path.display()))?;
+
+            layouts.retain(|existing| existing.name != layout.name);
+            layouts.push(layout);
+        }
+
+        Ok(layouts)
+    }
+
+    pub fn find(layouts: &[KeyboardLayout], name: &str) -> Result<KeyboardLayout> {
+        layouts
+            .iter()
+            .find(|layout| layout.name == name)
+            .cloned()
+            .ok_or_else(|| {
+                let names: Vec<&str> = layouts.iter().map(|layout| layout.name.as_str()).collect();
+                eyre!(
+                    "unknown layout '{}', expected one of {}",
+                    name,
+                    names.join(", ")
+                )
+            })
+    }
+
+    // Turns a key read from the terminal into what it would have typed on this layout. The
+    // terminal reads keys as qwerty, so the key is found by where it is on a qwerty keyboard
+    // and swapped for the key in the same place here. Anything else is left as it is
+    pub fn emulate(&self, keycode: KeyCode) -> KeyCode {
+        let KeyCode::Char(char) = keycode else {
+            return keycode;
+        };
+
+        let emulated = QWERTY_ROWS
+            .iter()
+            .zip(&self.rows)
+            .find_map(|((base, shifted), keys)| {
+                if let Some(column) = base.chars().position(|qwerty| qwerty == char) {
+                    return keys.get(column).map(|key| key.base);
+                }
+                let column = shifted.chars().position(|qwerty| qwerty == char)?;
+                keys.get(column).map(|key| key.shifted)
+            });
+
+        KeyCode::Char(emulated.unwrap_or(char))
+    }
+
+    pub fn rows(&self) -> &[Vec<Key>] {
+        &self.rows
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = Key> + '_ {
+        self.rows.iter().flatten().copied()
+    }
+
+    // the key that types the character, shifted or not
+    pub fn key_for(&self, char: char) -> Option<KeyboardKey> {
+        if char == ' ' {
+            return Some(KeyboardKey::Space);
+        }
+
+        self.rows.iter().enumerate().find_map(|(row, keys)| {
+            let column = keys.iter().position(|key| key.types(char))?;
+            Some(KeyboardKey::Typing {
+                key: keys[column],
+                row,
+                column,
+            })
+        })
+    }
+
+    // the shift key to hold for the character, if it needs one, which is pressed by the hand
+    // that isn't pressing the character's key
+    pub fn shift_for(&self, char: char) -> Option<KeyboardKey> {
+        let key = self.key_for(char)?;
+        match key {
+            KeyboardKey::Typing { key: typed, .. }
+                if typed.shifted == char && typed.base != char =>
+            {
+                Some(KeyboardKey::Shift(key.hand()?.other()))
+            }
+            _ => None,
+        }
+    }
+}
+
+pub const SPACE: Key = Key {
+    base: ' ',
+    shifted: ' ',
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_type_their_shifted_character() {
+        let layout = KeyboardLayout::qwerty();
+        let key_for = |char| match layout.key_for(char) {
+            Some(KeyboardKey::Typing { key, .. }) => Some(key.base),
+            _ => None,
+        };
+
+        assert_eq!(key_for('A'), Some('a'));
+        assert_eq!(key_for('?'), Some('/'));
+        assert_eq!(key_for('é'), None);
+        assert_eq!(layout.key_for(' '), Some(KeyboardKey::Space));
+    }
+
+    #[test]
+    fn keys_are_pressed_by_the_finger_for_where_they_are() {
+        let layout = KeyboardLayout::qwerty();
+        let finger = |char| layout.key_for(char).map(|key| (key.hand(), key.finger()));
+
+        assert_eq!(finger('f'), Some((Some(Hand::Left), Finger::Index)));
+        assert_eq!(finger('P'), Some((Some(Hand::Right), Finger::Pinky)));
+        assert_eq!(finger(' '), Some((None, Finger::Thumb)));
+    }
+
+    #[test]
+    fn shifted_characters_use_the_other_hands_shift() {
+        let layout = KeyboardLayout::qwerty();
+
+        assert_eq!(layout.shift_for('A'), Some(KeyboardKey::Shift(Hand::Right)));
+        assert_eq!(layout.shift_for('?'), Some(KeyboardKey::Shift(Hand::Left)));
+        assert_eq!(layout.shift_for('a'), None);
+        assert_eq!(layout.shift_for(' '), None);
+    }
+
+    #[test]
+    fn rows_keep_their_keys_in_order() {
+        let layout = KeyboardLayout::qwerty();
+        let home_row: String = layout.rows()[2].iter().map(|key| key.base).collect();
+
+        assert_eq!(home_row, "asdfghjkl;'");
+    }
+
+    #[test]
+    fn keys_are_swapped_for_the_one_in_the_same_place() {
+        let dvorak = KeyboardLayout::find(&KeyboardLayout::built_in(), "dvorak").unwrap();
+
+        assert_eq!(dvorak.emulate(KeyCode::Char('d')), KeyCode::Char('e'));
+        assert_eq!(dvorak.emulate(KeyCode::Char('D')), KeyCode::Char('E'));
+        assert_eq!(dvorak.emulate(KeyCode::Char('q')), KeyCode::Char('\''));
+        assert_eq!(dvorak.emulate(KeyCode::Char(' ')), KeyCode::Char(' '));
+        assert_eq!(dvorak.emulate(KeyCode::Backspace), KeyCode::Backspace);
+    }
+
+    #[test]
+    fn qwerty_types_what_was_pressed() {
+        let qwerty = KeyboardLayout::qwerty();
+
+        assert_eq!(qwerty.emulate(KeyCode::Char('d')), KeyCode::Char('d'));
+        assert_eq!(qwerty.emulate(KeyCode::Char('?')), KeyCode::Char('?'));
+    }
+
+    #[test]
+    fn built_in_layouts_are_named_after_their_files() {
+        let names: Vec<String> = KeyboardLayout::built_in()
+            .into_iter()
+            .map(|layout| layout.name)
+            .collect();
+
+        assert_eq!(names, vec!["qwerty", "colemak", "dvorak", "workman"]);
+    }
+
+    #[test]
+    fn short_row_points_at_its_line() {
+        let text = "number = ['`1234567890-=', '~!@#$%^&*()_+']\n\
+                    top = ['qwertyuiop', 'QWERTYUIOP']\n\
+                    home = [\"asdfghjkl;'\", 'ASDFGHJKL:\"']\n\
+                    bottom = ['zxcvbnm,./', 'ZXCVBNM<>?']";
+        let error = KeyboardLayout::from_toml("mine", text).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "line 2: the top row should have 13 keys with and without shift"
+        );
+    }
+
+    #[test]
+    fn finding_an_unknown_layout_lists_the_known_ones() {
+        let error = KeyboardLayout::find(&KeyboardLayout::built_in(), "azerty").unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "unknown layout 'azerty', expected one of qwerty, colemak, dvorak, workman"
+        );
+    }
+}
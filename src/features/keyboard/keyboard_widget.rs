@@ -0,0 +1,84 @@
+use ratatui::prelude::*;
+
+use super::{
+    finger::Hand,
+    keyboard_layout::{KeyboardKey, KeyboardLayout},
+};
+
+// each key is drawn three wide with a gap after it
+const KEY_WIDTH: u16 = 4;
+// how far each row is moved right of the one above, like the stagger of a real keyboard
+const ROW_INDENTS: [u16; 4] = [0, 2, 3, 5];
+// the space bar sits under the keys from c to m
+const SPACE_BAR_START: u16 = 2;
+const SPACE_BAR_KEYS: u16 = 5;
+// the shift keys fill the gaps either side of the bottom row
+const LEFT_SHIFT_WIDTH: u16 = 4;
+const RIGHT_SHIFT_WIDTH: u16 = 7;
+pub const KEYBOARD_HEIGHT: u16 = 5;
+pub const KEYBOARD_WIDTH: u16 = 53;
+
+// Draws the keys of a layout row by row with the space bar underneath, each key in whatever
+// style it's given
+pub struct KeyboardWidget<'a, F: Fn(KeyboardKey) -> Style> {
+    pub layout: &'a KeyboardLayout,
+    pub show_shift: bool,
+    pub key_style: F,
+}
+
+impl<F: Fn(KeyboardKey) -> Style> Widget for KeyboardWidget<'_, F> {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let mut draw = |x: u16, y: u16, label: &str, key: KeyboardKey| {
+            let width = label.chars().count() as u16;
+            if x + width <= area.width && y < area.height {
+                buf.set_string(area.x + x, area.y + y, label, (self.key_style)(key));
+            }
+        };
+
+        for (row, (keys, indent)) in self.layout.rows().iter().zip(ROW_INDENTS).enumerate() {
+            for (column, key) in keys.iter().enumerate() {
+                let x = indent + column as u16 * KEY_WIDTH;
+                let label = format!(" {} ", key.base);
+                draw(
+                    x,
+                    row as u16,
+                    &label,
+                    KeyboardKey::Typing {
+                        key: *key,
+                        row,
+                        column,
+                    },
+                );
+            }
+        }
+
+        let bottom_row = ROW_INDENTS.len() - 1;
+        let space_bar_width = (SPACE_BAR_KEYS * KEY_WIDTH - 1) as usize;
+        draw(
+            ROW_INDENTS[bottom_row] + SPACE_BAR_START * KEY_WIDTH,
+            KEYBOARD_HEIGHT - 1,
+            &format!("{:^width$}", "space", width = space_bar_width),
+            KeyboardKey::Space,
+        );
+
+        if self.show_shift {
+            let bottom_row_keys = self.layout.rows().get(bottom_row).map_or(0, Vec::len) as u16;
+            let shift_label = |width: u16| format!("{:^width$}", "⇧", width = width as usize);
+            draw(
+                0,
+                bottom_row as u16,
+                &shift_label(LEFT_SHIFT_WIDTH),
+                KeyboardKey::Shift(Hand::Left),
+            );
+            draw(
+                ROW_INDENTS[bottom_row] + bottom_row_keys * KEY_WIDTH,
+                bottom_row as u16,
+                &shift_label(RIGHT_SHIFT_WIDTH),
+                KeyboardKey::Shift(Hand::Right),
+            );
+        }
+    }
+}
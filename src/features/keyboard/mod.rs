@@ -0,0 +1,3 @@
+pub(crate) mod finger;
+pub(crate) mod keyboard_layout;
+pub(crate) mod keyboard_widget;
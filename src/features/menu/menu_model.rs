@@ -0,0 +1,144 @@
+use color_eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{
+    config::keybindings::Keybindings,
+    features::words_challenge::{
+        challenge_mode::ChallengeMode, challenge_settings::ChallengeSettings, quote::QuoteLength,
+        word_generator::WordBand,
+    },
+    tui::message::Message,
+};
+
+#[derive(Debug)]
+pub struct MenuModel {
+    // index into the list of modes
+    pub(super) selected: usize,
+    pub(super) word_band: WordBand,
+}
+
+impl MenuModel {
+    // starts with the given mode picked out, or the first when the menu doesn't offer it
+    pub fn new(default_mode: ChallengeMode) -> Self {
+        Self {
+            selected: Self::modes()
+                .iter()
+                .position(|mode| *mode == default_mode)
+                .unwrap_or(0),
+            word_band: WordBand::Top200,
+        }
+    }
+
+    // every mode the menu offers, in the order they are listed
+    pub(super) fn modes() -> Vec<ChallengeMode> {
+        let word_modes = ChallengeMode::WORD_COUNTS.map(ChallengeMode::Words);
+        let time_modes = ChallengeMode::TIME_LIMITS.map(ChallengeMode::Time);
+        let quote_modes = QuoteLength::ALL.map(ChallengeMode::Quote);
+        word_modes
+            .into_iter()
+            .chain(time_modes)
+            .chain(quote_modes)
+            .chain([ChallengeMode::Adaptive(ChallengeMode::ADAPTIVE_WORD_COUNT)])
+            .collect()
+    }
+
+    pub(super) fn selected_settings(&self) -> ChallengeSettings {
+        ChallengeSettings::new(Self::modes()[self.selected], self.word_band)
+    }
+
+    pub fn handle_event(
+        &self,
+        key_event: KeyEvent,
+        keybindings: &Keybindings,
+    ) -> Result<Option<Message>> {
+        let message = match key_event.code {
+            key if key == keybindings.history => Message::OpenHistory,
+            keycode => Message::MenuInput(keycode),
+        };
+
+        Ok(Some(message))
+    }
+
+    pub(super) fn handle_menu_input(self, keycode: KeyCode) -> Self {
+        let mode_count = Self::modes().len();
+
+        match keycode {
+            KeyCode::Down | KeyCode::Char('j') => Self {
+                selected: (self.selected + 1) % mode_count,
+                ..self
+            },
+            KeyCode::Up | KeyCode::Char('k') => Self {
+                selected: (self.selected + mode_count - 1) % mode_count,
+                ..self
+            },
+            KeyCode::Tab | KeyCode::Right | KeyCode::Left => Self {
+                word_band: self.word_band.next(),
+                ..self
+            },
+            _ => self,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moving_down_from_last_mode_wraps_to_first() {
+        let model = MenuModel {
+            selected: MenuModel::modes().len() - 1,
+            word_band: WordBand::Top200,
+        };
+
+        let result = model.handle_menu_input(KeyCode::Down);
+        assert_eq!(result.selected, 0);
+    }
+
+    #[test]
+    fn moving_up_from_first_mode_wraps_to_last() {
+        let model = MenuModel {
+            selected: 0,
+            word_band: WordBand::Top200,
+        };
+
+        let result = model.handle_menu_input(KeyCode::Up);
+        assert_eq!(result.selected, MenuModel::modes().len() - 1);
+    }
+
+    #[test]
+    fn selected_settings_use_chosen_mode_and_band() {
+        let model = MenuModel::new(ChallengeMode::Words(25))
+            .handle_menu_input(KeyCode::Tab)
+            .handle_menu_input(KeyCode::Down);
+
+        let settings = model.selected_settings();
+        assert_eq!(settings.mode, ChallengeMode::Words(50));
+        assert_eq!(settings.word_band, WordBand::Top1k);
+    }
+
+    #[test]
+    fn default_mode_is_picked_out() {
+        let model = MenuModel::new(ChallengeMode::Time(60));
+
+        assert_eq!(model.selected_settings().mode, ChallengeMode::Time(60));
+    }
+
+    #[test]
+    fn configured_history_key_opens_the_history() {
+        let keybindings = Keybindings {
+            history: KeyCode::F(2),
+            ..Keybindings::default()
+        };
+        let model = MenuModel::new(ChallengeMode::Words(25));
+
+        assert!(matches!(
+            model.handle_event(KeyEvent::from(KeyCode::F(2)), &keybindings).unwrap(),
+            Some(Message::OpenHistory)
+        ));
+        assert!(matches!(
+            model.handle_event(KeyEvent::from(KeyCode::Char('h')), &keybindings).unwrap(),
+            Some(Message::MenuInput(KeyCode::Char('h')))
+        ));
+    }
+}
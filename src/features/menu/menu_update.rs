@@ -0,0 +1,63 @@
+use crossterm::event::KeyCode;
+
+use crate::tui::{app_model::AppModel, app_page::AppPage, message::Message};
+use color_eyre::Result;
+
+pub fn menu_update(model: AppModel, message: Message) -> Result<(AppModel, Option<Message>)> {
+    let menu = match model.app_page {
+        AppPage::Menu(menu) => menu,
+        _ => panic!(
+            "menu cannot use non menu models, found: {:?}",
+            model.app_page
+        ),
+    };
+
+    match message {
+        Message::MenuInput(KeyCode::Enter) => {
+            let start_message = Message::StartChallenge(menu.selected_settings(), rand::random());
+            Ok((
+                AppModel {
+                    app_page: AppPage::Menu(menu),
+                    ..model
+                },
+                Some(start_message),
+            ))
+        }
+        Message::MenuInput(KeyCode::Char('s')) => Ok((
+            AppModel {
+                app_page: AppPage::Menu(menu),
+                ..model
+            },
+            Some(Message::OpenStats),
+        )),
+        Message::MenuInput(KeyCode::Char('/')) => Ok((
+            AppModel {
+                app_page: AppPage::Menu(menu),
+                ..model
+            },
+            Some(Message::OpenQuoteSearch),
+        )),
+        Message::MenuInput(KeyCode::Char('g')) => Ok((
+            AppModel {
+                app_page: AppPage::Menu(menu),
+                ghost_source: model.ghost_source.next(),
+                ..model
+            },
+            None,
+        )),
+        Message::MenuInput(keycode) => Ok((
+            AppModel {
+                app_page: AppPage::Menu(menu.handle_menu_input(keycode)),
+                ..model
+            },
+            None,
+        )),
+        _ => Ok((
+            AppModel {
+                app_page: AppPage::Menu(menu),
+                ..model
+            },
+            None,
+        )),
+    }
+}
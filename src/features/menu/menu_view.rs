@@ -0,0 +1,79 @@
+use ratatui::layout::Flex;
+use ratatui::prelude::*;
+use ratatui::widgets::{List, ListState, Paragraph, Wrap};
+use ratatui::Frame;
+
+use crate::config::{app_config::AppConfig, keybindings::key_name};
+use crate::features::words_challenge::ghost::GhostSource;
+
+use super::menu_model::MenuModel;
+
+// Lists the modes that can be started in a column in the middle of the screen, with any problem
+// reloading the config file underneath
+pub fn menu_view(
+    menu: &MenuModel,
+    ghost_source: GhostSource,
+    config: &AppConfig,
+    config_error: Option<&str>,
+    frame: &mut Frame,
+) {
+    let modes = MenuModel::modes();
+
+    let horizontal_center = Layout::horizontal([Constraint::Length(60)]).flex(Flex::Center).split(frame.area())[0];
+    let [title_area, _, list_area, _, band_area, ghost_area, help_area, _, error_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Length(modes.len() as u16),
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Length(2),
+        Constraint::Length(1),
+        Constraint::Length(3),
+    ])
+    .flex(Flex::Center)
+    .areas(horizontal_center);
+
+    let theme = &config.theme;
+    let label = Style::new().fg(theme.label);
+
+    let title = Paragraph::new("typee").style(Style::new().fg(theme.accent).bold());
+
+    let list = List::new(modes.iter().map(|mode| mode.to_string()))
+        .style(Style::new().fg(theme.secondary))
+        .highlight_style(Style::new().fg(theme.accent))
+        .highlight_symbol("> ");
+    let mut list_state = ListState::default().with_selected(Some(menu.selected));
+
+    let band = Line::from(vec![
+        Span::styled("words ", label),
+        Span::raw(menu.word_band.to_string()),
+    ]);
+
+    let ghost = Line::from(vec![
+        Span::styled("ghost ", label),
+        Span::raw(ghost_source.to_string()),
+    ]);
+
+    let help = Paragraph::new(vec![
+        Line::raw(format!("↑/↓ select · enter start · {} quit", key_name(config.keybindings.quit))),
+        Line::raw(format!(
+            "tab words · g ghost · / find quote · {} history · s stats",
+            key_name(config.keybindings.history)
+        )),
+    ])
+    .style(label);
+
+    frame.render_widget(title, title_area);
+    frame.render_stateful_widget(list, list_area, &mut list_state);
+    frame.render_widget(Paragraph::new(band), band_area);
+    frame.render_widget(Paragraph::new(ghost), ghost_area);
+    frame.render_widget(help, help_area);
+
+    if let Some(config_error) = config_error {
+        let error = Paragraph::new(config_error)
+            .style(Style::new().fg(theme.warning))
+            .wrap(Wrap { trim: true });
+        frame.render_widget(error, error_area);
+    }
+}
@@ -0,0 +1,3 @@
+pub(crate) mod menu_model;
+pub(crate) mod menu_update;
+pub(crate) mod menu_view;
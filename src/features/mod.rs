@@ -1 +1,9 @@
+pub(crate) mod adaptive;
+pub(crate) mod history;
+pub(crate) mod keyboard;
+pub(crate) mod menu;
+pub(crate) mod quote_search;
+pub(crate) mod replay;
+pub(crate) mod results;
+pub(crate) mod stats;
 pub(crate) mod words_challenge;
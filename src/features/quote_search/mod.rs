@@ -0,0 +1,3 @@
+pub(crate) mod quote_search_model;
+pub(crate) mod quote_search_update;
+pub(crate) mod quote_search_view;
@@ -0,0 +1,131 @@
+use color_eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{features::words_challenge::quote::Quote, tui::message::Message};
+
+#[derive(Debug)]
+pub struct QuoteSearchModel {
+    // what has been typed to search for
+    pub(super) query: String,
+    // the quotes whose text or source match the query
+    pub(super) matches: Vec<&'static Quote>,
+    // index into matches
+    pub(super) selected: usize,
+}
+
+impl QuoteSearchModel {
+    pub fn new() -> Self {
+        Self {
+            query: String::new(),
+            matches: Quote::search(""),
+            selected: 0,
+        }
+    }
+
+    pub(super) fn selected_quote(&self) -> Option<&'static Quote> {
+        self.matches.get(self.selected).copied()
+    }
+
+    pub fn handle_event(&self, key_event: KeyEvent) -> Result<Option<Message>> {
+        let message = match key_event.code {
+            KeyCode::Esc => Message::OpenMenu,
+            KeyCode::Enter => match self.selected_quote() {
+                Some(quote) => Message::StartQuote(quote.id),
+                None => return Ok(None),
+            },
+            keycode => Message::QuoteSearchInput(keycode),
+        };
+
+        Ok(Some(message))
+    }
+
+    pub(super) fn handle_search_input(self, keycode: KeyCode) -> Self {
+        let last_match = self.matches.len().saturating_sub(1);
+
+        match keycode {
+            KeyCode::Down => Self {
+                selected: (self.selected + 1).min(last_match),
+                ..self
+            },
+            KeyCode::Up => Self {
+                selected: self.selected.saturating_sub(1),
+                ..self
+            },
+            KeyCode::Char(char) => {
+                let mut query = self.query;
+                query.push(char);
+                Self::with_query(query)
+            }
+            KeyCode::Backspace => {
+                let mut query = self.query;
+                query.pop();
+                Self::with_query(query)
+            }
+            _ => self,
+        }
+    }
+
+    // searching again always goes back to the first match
+    fn with_query(query: String) -> Self {
+        Self {
+            matches: Quote::search(&query),
+            query,
+            selected: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn search_for(query: &str) -> QuoteSearchModel {
+        query.chars().fold(QuoteSearchModel::new(), |model, char| {
+            model.handle_search_input(KeyCode::Char(char))
+        })
+    }
+
+    #[test]
+    fn empty_search_lists_every_quote() {
+        assert_eq!(QuoteSearchModel::new().matches.len(), Quote::all().len());
+    }
+
+    #[test]
+    fn typing_narrows_matches() {
+        let model = search_for("lincoln");
+
+        assert_eq!(model.query, "lincoln");
+        assert!(!model.matches.is_empty());
+        assert!(model.matches.iter().all(|quote| quote.source.contains("Lincoln")));
+    }
+
+    #[test]
+    fn backspace_widens_matches() {
+        let narrow = search_for("lincolnx");
+        assert!(narrow.matches.is_empty());
+
+        let wider = narrow.handle_search_input(KeyCode::Backspace);
+        assert!(!wider.matches.is_empty());
+    }
+
+    #[test]
+    fn selection_stays_within_matches() {
+        let model = search_for("gettysburg")
+            .handle_search_input(KeyCode::Down)
+            .handle_search_input(KeyCode::Down);
+
+        assert_eq!(model.selected, 0);
+        assert!(model.selected_quote().is_some());
+    }
+
+    #[test]
+    fn enter_starts_selected_quote() {
+        let model = search_for("gettysburg");
+        let message = model
+            .handle_event(KeyEvent::from(KeyCode::Enter))
+            .unwrap();
+
+        let gettysburg = Quote::search("gettysburg")[0];
+        assert!(matches!(message, Some(Message::StartQuote(id)) if id == gettysburg.id));
+    }
+}
@@ -0,0 +1,25 @@
+use crate::tui::{app_model::AppModel, app_page::AppPage, message::Message};
+use color_eyre::Result;
+
+pub fn quote_search_update(model: AppModel, message: Message) -> Result<(AppModel, Option<Message>)> {
+    let search = match model.app_page {
+        AppPage::QuoteSearch(search) => search,
+        _ => panic!(
+            "quote search cannot use non quote search models, found: {:?}",
+            model.app_page
+        ),
+    };
+
+    let new_search = match message {
+        Message::QuoteSearchInput(keycode) => search.handle_search_input(keycode),
+        _ => search,
+    };
+
+    Ok((
+        AppModel {
+            app_page: AppPage::QuoteSearch(new_search),
+            ..model
+        },
+        None,
+    ))
+}
@@ -0,0 +1,56 @@
+use ratatui::layout::Flex;
+use ratatui::prelude::*;
+use ratatui::widgets::{List, ListItem, ListState, Paragraph};
+use ratatui::Frame;
+
+use crate::config::theme::Theme;
+
+use super::quote_search_model::QuoteSearchModel;
+
+// Shows the search box at the top of an 80 column box with the matching quotes listed under it,
+// each with its source on the line below
+pub fn quote_search_view(search: &QuoteSearchModel, theme: &Theme, frame: &mut Frame) {
+    let horizontal_center = Layout::horizontal([Constraint::Length(80)]).flex(Flex::Center).split(frame.area())[0];
+    let [title_area, query_area, _, list_area, _, help_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Fill(1),
+        Constraint::Length(1),
+        Constraint::Length(1),
+    ])
+    .areas(horizontal_center.inner(Margin::new(0, 1)));
+
+    let highlight = Style::new().fg(theme.accent);
+    let label = Style::new().fg(theme.label);
+
+    let title = Paragraph::new("quotes").style(highlight.bold());
+
+    let query = Line::from(vec![
+        Span::styled("search ", label),
+        Span::raw(search.query.as_str()),
+        Span::styled("_", highlight),
+        Span::styled(format!("   {} quotes", search.matches.len()), label),
+    ]);
+
+    // long quotes are cut off at the edge of the box, the start is enough to recognise them by
+    let items = search.matches.iter().map(|quote| {
+        ListItem::new(vec![
+            Line::raw(format!("{} · {}", quote.length(), quote.text)),
+            Line::styled(format!("    {}", quote.source), label),
+        ])
+    });
+    let list = List::new(items)
+        .style(Style::new().fg(theme.secondary))
+        .highlight_style(highlight)
+        .highlight_symbol("> ");
+    let mut list_state = ListState::default().with_selected(Some(search.selected));
+
+    let help = Paragraph::new("type to search text or author · ↑/↓ select · enter start · esc back")
+        .style(label);
+
+    frame.render_widget(title, title_area);
+    frame.render_widget(Paragraph::new(query), query_area);
+    frame.render_stateful_widget(list, list_area, &mut list_state);
+    frame.render_widget(help, help_area);
+}
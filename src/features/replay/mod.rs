@@ -0,0 +1,3 @@
+pub(crate) mod replay_model;
+pub(crate) mod replay_update;
+pub(crate) mod replay_view;
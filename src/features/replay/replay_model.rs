@@ -0,0 +1,297 @@
+use std::time::{Duration, Instant};
+
+use color_eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{
+    features::{
+        results::results_model::ResultsModel,
+        words_challenge::words_challenge_model::WordsChallengeModel,
+    },
+    tui::message::Message,
+};
+
+// the playback speeds that can be stepped through, as multiples of the original typing speed
+pub const REPLAY_SPEEDS: [f32; 6] = [0.5, 1.0, 1.5, 2.0, 3.0, 4.0];
+// how far seeking moves playback
+const SEEK_STEP: Duration = Duration::from_secs(5);
+
+// Plays a finished challenge back by feeding its recorded keystrokes into a fresh challenge with
+// the same words at the times they were originally typed
+#[derive(Debug)]
+pub struct ReplayModel {
+    // the results being replayed, returned to once the replay is closed
+    results: ResultsModel,
+    pub(super) challenge: WordsChallengeModel,
+    // how many of the recorded keystrokes have been fed into the challenge
+    applied_keystrokes: usize,
+    // how far into the recording playback has reached
+    pub(super) position: Duration,
+    // index into the replay speeds
+    speed_index: usize,
+    pub(super) paused: bool,
+    last_tick: Option<Instant>,
+}
+
+impl ReplayModel {
+    pub fn new(results: ResultsModel) -> Self {
+        let challenge = WordsChallengeModel::same_text_as(results.result());
+
+        Self {
+            results,
+            challenge,
+            applied_keystrokes: 0,
+            position: Duration::ZERO,
+            speed_index: 1,
+            paused: false,
+            last_tick: None,
+        }
+    }
+
+    pub fn into_results(self) -> ResultsModel {
+        self.results
+    }
+
+    pub(super) fn speed(&self) -> f32 {
+        REPLAY_SPEEDS[self.speed_index]
+    }
+
+    // the length of the recording, which ends either when time ran out or with the last keystroke
+    pub(super) fn duration(&self) -> Duration {
+        let result = self.results.result();
+        let last_keystroke = result.keystrokes.last().map(|keystroke| keystroke.time);
+        result.duration.max(last_keystroke.unwrap_or_default())
+    }
+
+    pub(super) fn is_at_end(&self) -> bool {
+        self.position >= self.duration()
+    }
+
+    // moves playback on by the time since the last tick, scaled by the playback speed
+    pub fn tick(self, now: Instant) -> Self {
+        let since_last_tick = self.last_tick.map_or(Duration::ZERO, |last_tick| now - last_tick);
+        let replay = Self {
+            last_tick: Some(now),
+            ..self
+        };
+
+        if replay.paused {
+            return replay;
+        }
+
+        let position = replay.position + since_last_tick.mul_f32(replay.speed());
+        let played = replay.seek_to(position);
+
+        // playback stops at the end so the finished text stays on screen
+        let paused = played.is_at_end();
+        Self { paused, ..played }
+    }
+
+    // Shows the challenge as it was at the given time. Keystrokes can only be played forwards
+    // so going back starts again from the beginning, which is quick for the length of a test
+    fn seek_to(self, position: Duration) -> Self {
+        let position = position.min(self.duration());
+
+        let (mut challenge, mut applied_keystrokes) = if position < self.position {
+            (WordsChallengeModel::same_text_as(self.results.result()), 0)
+        } else {
+            (self.challenge, self.applied_keystrokes)
+        };
+
+        let keystrokes = &self.results.result().keystrokes;
+        while let Some(keystroke) = keystrokes.get(applied_keystrokes) {
+            if keystroke.time > position {
+                break;
+            }
+            challenge = challenge.replay_keystroke(keystroke);
+            applied_keystrokes += 1;
+        }
+
+        Self {
+            challenge,
+            applied_keystrokes,
+            position,
+            ..self
+        }
+    }
+
+    pub fn handle_event(&self, key_event: KeyEvent) -> Result<Option<Message>> {
+        let message = match key_event.code {
+            KeyCode::Char('b') | KeyCode::Backspace => Message::CloseReplay,
+            keycode => Message::ReplayInput(keycode),
+        };
+
+        Ok(Some(message))
+    }
+
+    pub(super) fn handle_replay_input(self, keycode: KeyCode) -> Self {
+        match keycode {
+            // playing again once the end is reached starts from the beginning
+            KeyCode::Char(' ') if self.paused && self.is_at_end() => Self {
+                paused: false,
+                ..self.seek_to(Duration::ZERO)
+            },
+            KeyCode::Char(' ') => Self {
+                paused: !self.paused,
+                ..self
+            },
+            KeyCode::Left | KeyCode::Char('h') => {
+                let position = self.position.saturating_sub(SEEK_STEP);
+                self.seek_to(position)
+            }
+            KeyCode::Right | KeyCode::Char('l') => {
+                let position = self.position + SEEK_STEP;
+                self.seek_to(position)
+            }
+            KeyCode::Home | KeyCode::Char('0') => self.seek_to(Duration::ZERO),
+            KeyCode::Up | KeyCode::Char('+') | KeyCode::Char('=') => Self {
+                speed_index: (self.speed_index + 1).min(REPLAY_SPEEDS.len() - 1),
+                ..self
+            },
+            KeyCode::Down | KeyCode::Char('-') => Self {
+                speed_index: self.speed_index.saturating_sub(1),
+                ..self
+            },
+            _ => self,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Local;
+
+    use crate::features::words_challenge::{
+        challenge_mode::ChallengeMode,
+        challenge_result::ChallengeResult,
+        challenge_settings::ChallengeSettings,
+        keystroke::{Keystroke, KeystrokeKey},
+        stop_on_error::StopOnError,
+        word_generator::{WordBand, WordGenerator},
+    };
+
+    use super::*;
+
+    // a one word challenge typed correctly with a keystroke every second
+    fn replay() -> ReplayModel {
+        let word = WordGenerator::new(WordBand::Top200, 0).next_word();
+        let keystrokes: Vec<Keystroke> = word
+            .chars()
+            .enumerate()
+            .map(|(position, char)| Keystroke {
+                key: KeystrokeKey::Character(char),
+                expected: Some(char),
+                position,
+                time: Duration::from_secs(position as u64),
+            })
+            .collect();
+
+        let result = ChallengeResult {
+            settings: ChallengeSettings {
+                mode: ChallengeMode::Words(1),
+                word_band: WordBand::Top200,
+            },
+            seed: 0,
+            wpm: 20.0,
+            accuracy: 100.0,
+            duration: Duration::from_secs(word.len() as u64 - 1),
+            correct_characters: word.len(),
+            incorrect_characters: 0,
+            completed_at: Local::now(),
+            wpm_samples: Vec::new(),
+            keystrokes,
+            quote_id: None,
+            custom_text: None,
+            stop_on_error: StopOnError::Off,
+            failure: None,
+            mistyped_words: Vec::new(),
+        };
+
+        ReplayModel::new(ResultsModel::new(result, None))
+    }
+
+    #[test]
+    fn first_keystroke_plays_straight_away() {
+        let replay = replay().tick(Instant::now());
+        assert_eq!(replay.applied_keystrokes, 1);
+    }
+
+    #[test]
+    fn ticking_plays_keystrokes_up_to_elapsed_time() {
+        let start = Instant::now();
+        let replay = replay()
+            .tick(start)
+            .tick(start + Duration::from_millis(1_500));
+
+        assert_eq!(replay.position, Duration::from_millis(1_500));
+        assert_eq!(replay.applied_keystrokes, 2);
+    }
+
+    #[test]
+    fn faster_speed_plays_further_in_the_same_time() {
+        let start = Instant::now();
+        let replay = replay()
+            .handle_replay_input(KeyCode::Char('+'))
+            .tick(start)
+            .tick(start + Duration::from_secs(1));
+
+        assert_eq!(replay.speed(), 1.5);
+        assert_eq!(replay.position, Duration::from_millis(1_500));
+    }
+
+    #[test]
+    fn speed_is_limited_to_slowest_and_fastest() {
+        let slowest = replay()
+            .handle_replay_input(KeyCode::Char('-'))
+            .handle_replay_input(KeyCode::Char('-'));
+        assert_eq!(slowest.speed(), 0.5);
+
+        let fastest = (0..10).fold(replay(), |replay, _| replay.handle_replay_input(KeyCode::Char('+')));
+        assert_eq!(fastest.speed(), 4.0);
+    }
+
+    #[test]
+    fn paused_replay_does_not_move() {
+        let start = Instant::now();
+        let replay = replay()
+            .handle_replay_input(KeyCode::Char(' '))
+            .tick(start)
+            .tick(start + Duration::from_secs(2));
+
+        assert_eq!(replay.position, Duration::ZERO);
+    }
+
+    #[test]
+    fn seeking_back_replays_from_the_beginning() {
+        let replay = replay()
+            .handle_replay_input(KeyCode::Right)
+            .handle_replay_input(KeyCode::Home);
+
+        assert_eq!(replay.position, Duration::ZERO);
+        assert_eq!(replay.applied_keystrokes, 1);
+    }
+
+    #[test]
+    fn replay_pauses_at_the_end() {
+        let start = Instant::now();
+        let replay = replay()
+            .tick(start)
+            .tick(start + Duration::from_secs(60));
+
+        assert!(replay.paused);
+        assert!(replay.is_at_end());
+        assert!(replay.challenge.is_finished());
+    }
+
+    #[test]
+    fn playing_from_the_end_restarts() {
+        let replay = replay()
+            .handle_replay_input(KeyCode::Right)
+            .handle_replay_input(KeyCode::Right)
+            .handle_replay_input(KeyCode::Char(' '))
+            .handle_replay_input(KeyCode::Char(' '));
+
+        assert!(!replay.paused);
+        assert_eq!(replay.position, Duration::ZERO);
+    }
+}
@@ -0,0 +1,29 @@
+use std::time::Instant;
+
+use crate::tui::{app_model::AppModel, app_page::AppPage, message::Message};
+use color_eyre::Result;
+
+pub fn replay_update(model: AppModel, message: Message) -> Result<(AppModel, Option<Message>)> {
+    let replay = match model.app_page {
+        AppPage::Replay(replay) => replay,
+        _ => panic!(
+            "replay cannot use non replay models, found: {:?}",
+            model.app_page
+        ),
+    };
+
+    let page = match message {
+        Message::ReplayInput(keycode) => AppPage::Replay(replay.handle_replay_input(keycode)),
+        Message::Tick => AppPage::Replay(replay.tick(Instant::now())),
+        Message::CloseReplay => AppPage::Results(replay.into_results()),
+        _ => AppPage::Replay(replay),
+    };
+
+    Ok((
+        AppModel {
+            app_page: page,
+            ..model
+        },
+        None,
+    ))
+}
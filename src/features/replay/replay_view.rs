@@ -0,0 +1,53 @@
+use std::time::Duration;
+
+use ratatui::layout::Flex;
+use ratatui::prelude::*;
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+use crate::config::app_config::AppConfig;
+use crate::features::words_challenge::words_challenge_widget::{WordsChallengeWidget, VISIBLE_LINES};
+
+use super::replay_model::ReplayModel;
+
+// Draws the replayed challenge the same way as a live one, with the playback position and
+// controls in place of the hud
+pub fn replay_view(replay: &ReplayModel, config: &AppConfig, frame: &mut Frame) {
+    let horizontal_center = Layout::horizontal([Constraint::Length(80)]).flex(Flex::Center).split(frame.area())[0];
+    let [hud_area, _, text_area, _, help_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Length(VISIBLE_LINES),
+        Constraint::Length(1),
+        Constraint::Length(1),
+    ])
+    .flex(Flex::Center)
+    .areas(horizontal_center);
+
+    let label = Style::new().fg(config.theme.label);
+
+    let state = if replay.paused { "paused" } else { "playing" };
+    let hud = Line::from(vec![
+        Span::styled(
+            format!("{} / {}", format_position(replay.position), format_position(replay.duration())),
+            Style::new().fg(config.theme.hud),
+        ),
+        Span::styled(format!("  {}x  {}", replay.speed(), state), label),
+    ]);
+
+    let challenge_widget = WordsChallengeWidget {
+        challenge_model: &replay.challenge,
+        caret_style: config.caret_style,
+        theme: &config.theme,
+    };
+
+    let help = Paragraph::new("space pause · ←/→ seek · +/- speed · 0 restart · b back").style(label);
+
+    frame.render_widget(Paragraph::new(hud), hud_area);
+    frame.render_widget(challenge_widget, text_area);
+    frame.render_widget(help, help_area);
+}
+
+fn format_position(position: Duration) -> String {
+    format!("{:.1}s", position.as_secs_f32())
+}
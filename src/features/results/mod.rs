@@ -0,0 +1,4 @@
+pub(crate) mod results_model;
+pub(crate) mod results_update;
+pub(crate) mod results_view;
+mod wpm_chart_widget;
@@ -0,0 +1,193 @@
+use color_eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{
+    config::keybindings::Keybindings,
+    features::{
+        stats::{key_stats::KeyStats, keyboard_heatmap_widget::HeatmapMode},
+        words_challenge::{challenge_mode::ChallengeMode, challenge_result::ChallengeResult},
+    },
+    tui::message::Message,
+};
+
+#[derive(Debug)]
+pub struct ResultsModel {
+    pub(super) result: ChallengeResult,
+    // the best wpm for the same test before this one, if it had been done before
+    pub(super) previous_best: Option<f32>,
+    // how each key was typed in this challenge, for the heatmap
+    pub(super) key_stats: KeyStats,
+    pub(super) heatmap: HeatmapMode,
+}
+
+impl ResultsModel {
+    pub fn new(result: ChallengeResult, previous_best: Option<f32>) -> Self {
+        Self {
+            key_stats: KeyStats::from_keystrokes(&result.keystrokes),
+            result,
+            previous_best,
+            heatmap: HeatmapMode::default(),
+        }
+    }
+
+    pub fn result(&self) -> &ChallengeResult {
+        &self.result
+    }
+
+    // a failed result is never a personal best, however fast it was
+    pub(super) fn is_personal_best(&self) -> bool {
+        if self.result.failure.is_some() {
+            return false;
+        }
+
+        match self.previous_best {
+            Some(previous_best) => self.result.wpm > previous_best,
+            None => true,
+        }
+    }
+
+    pub(super) fn switch_heatmap(self) -> Self {
+        Self {
+            heatmap: self.heatmap.next(),
+            ..self
+        }
+    }
+
+    pub fn handle_event(
+        &self,
+        key_event: KeyEvent,
+        keybindings: &Keybindings,
+    ) -> Result<Option<Message>> {
+        let is_adaptive = matches!(self.result.settings.mode, ChallengeMode::Adaptive(_));
+        let message = match key_event.code {
+            // the same seed regenerates the same words, a quote is looked up again by its id and
+            // custom text and adaptive lessons are kept with the result
+            key if key == keybindings.retry => match (&self.result.custom_text, self.result.quote_id) {
+                (Some(text), _) if is_adaptive => Some(Message::StartAdaptiveText(text.clone())),
+                (Some(text), _) => Some(Message::StartCustomText(text.clone())),
+                (None, Some(quote_id)) => Some(Message::StartQuote(quote_id)),
+                (None, None) => Some(Message::StartChallenge(self.result.settings, self.result.seed)),
+            },
+            // custom text moves on to its next chunk rather than anything random, and a new
+            // adaptive lesson is made up from the progress including this one
+            key if key == keybindings.new_test || key == KeyCode::Enter => {
+                match self.result.settings.mode {
+                    ChallengeMode::Custom(_) => Some(Message::StartNextCustomChunk),
+                    _ => Some(Message::StartChallenge(self.result.settings, rand::random())),
+                }
+            }
+            // the practice is typed like any other custom text
+            key if key == keybindings.practise_missed => {
+                self.result.practice_text().map(Message::StartCustomText)
+            }
+            key if key == keybindings.menu => Some(Message::OpenMenu),
+            key if key == keybindings.history => Some(Message::OpenHistory),
+            // results saved before keystrokes were recorded have nothing to replay
+            key if key == keybindings.replay && !self.result.keystrokes.is_empty() => {
+                Some(Message::OpenReplay)
+            }
+            key if key == keybindings.heatmap && !self.key_stats.is_empty() => {
+                Some(Message::SwitchHeatmap)
+            }
+            _ => None,
+        };
+
+        Ok(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chrono::Local;
+
+    use crate::features::words_challenge::{
+        challenge_settings::ChallengeSettings, fail_conditions::FailReason,
+        stop_on_error::StopOnError, word_generator::WordBand,
+    };
+
+    use super::*;
+
+    fn results_with_wpm(wpm: f32, previous_best: Option<f32>) -> ResultsModel {
+        let result = ChallengeResult {
+            settings: ChallengeSettings {
+                mode: ChallengeMode::Words(25),
+                word_band: WordBand::Top200,
+            },
+            seed: 0,
+            wpm,
+            accuracy: 100.0,
+            duration: Duration::from_secs(20),
+            correct_characters: 100,
+            incorrect_characters: 0,
+            completed_at: Local::now(),
+            wpm_samples: Vec::new(),
+            keystrokes: Vec::new(),
+            quote_id: None,
+            custom_text: None,
+            stop_on_error: StopOnError::Off,
+            failure: None,
+            mistyped_words: Vec::new(),
+        };
+
+        ResultsModel::new(result, previous_best)
+    }
+
+    #[test]
+    fn first_result_is_personal_best() {
+        assert!(results_with_wpm(40.0, None).is_personal_best());
+    }
+
+    #[test]
+    fn beating_previous_best_is_personal_best() {
+        assert!(results_with_wpm(80.5, Some(80.0)).is_personal_best());
+    }
+
+    #[test]
+    fn matching_previous_best_is_not_personal_best() {
+        assert!(!results_with_wpm(80.0, Some(80.0)).is_personal_best());
+    }
+
+    #[test]
+    fn retrying_an_adaptive_lesson_types_the_same_words() {
+        let mut results = results_with_wpm(40.0, None);
+        results.result.settings =
+            ChallengeSettings::new(ChallengeMode::Adaptive(2), WordBand::Top200);
+        results.result.custom_text = Some("tine lent".to_string());
+        let keybindings = Keybindings::default();
+        let press = |code| results.handle_event(KeyEvent::from(code), &keybindings).unwrap();
+
+        assert!(matches!(
+            press(keybindings.retry),
+            Some(Message::StartAdaptiveText(text)) if text == "tine lent"
+        ));
+        assert!(matches!(
+            press(KeyCode::Enter),
+            Some(Message::StartChallenge(settings, _)) if settings == results.result.settings
+        ));
+    }
+
+    #[test]
+    fn practising_missed_words_types_only_those() {
+        let mut results = results_with_wpm(40.0, None);
+        let keybindings = Keybindings::default();
+        let practise_missed = KeyEvent::from(keybindings.practise_missed);
+
+        assert!(results.handle_event(practise_missed, &keybindings).unwrap().is_none());
+
+        results.result.mistyped_words = vec!["fox".to_string(), "lazy".to_string()];
+        assert!(matches!(
+            results.handle_event(practise_missed, &keybindings).unwrap(),
+            Some(Message::StartCustomText(text)) if text == "fox fox fox lazy lazy lazy"
+        ));
+    }
+
+    #[test]
+    fn failed_result_is_never_personal_best() {
+        let mut results = results_with_wpm(80.0, None);
+        results.result.failure = Some(FailReason::Mistake);
+
+        assert!(!results.is_personal_best());
+    }
+}
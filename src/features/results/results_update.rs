@@ -0,0 +1,39 @@
+use crate::{
+    features::replay::replay_model::ReplayModel,
+    tui::{app_model::AppModel, app_page::AppPage, message::Message},
+};
+use color_eyre::Result;
+
+pub fn results_update(model: AppModel, message: Message) -> Result<(AppModel, Option<Message>)> {
+    let results = match model.app_page {
+        AppPage::Results(results) => results,
+        _ => panic!(
+            "results cannot use non results models, found: {:?}",
+            model.app_page
+        ),
+    };
+
+    match message {
+        Message::OpenReplay => Ok((
+            AppModel {
+                app_page: AppPage::Replay(ReplayModel::new(results)),
+                ..model
+            },
+            None,
+        )),
+        Message::SwitchHeatmap => Ok((
+            AppModel {
+                app_page: AppPage::Results(results.switch_heatmap()),
+                ..model
+            },
+            None,
+        )),
+        _ => Ok((
+            AppModel {
+                app_page: AppPage::Results(results),
+                ..model
+            },
+            None,
+        )),
+    }
+}
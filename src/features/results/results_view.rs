@@ -0,0 +1,148 @@
+use ratatui::layout::Flex;
+use ratatui::prelude::*;
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+use crate::config::{app_config::AppConfig, keybindings::key_name};
+use crate::features::keyboard::keyboard_widget::KEYBOARD_HEIGHT;
+use crate::features::stats::keyboard_heatmap_widget::KeyboardHeatmapWidget;
+use crate::features::words_challenge::quote::Quote;
+
+use super::results_model::ResultsModel;
+use super::wpm_chart_widget::WpmChartWidget;
+
+// the results without the heatmap, it's left out when there isn't room for both
+const RESULTS_HEIGHT: u16 = 22;
+
+// Shows the scores of a finished challenge in the middle of the screen, with a chart of the wpm
+// over time, a heatmap of the keys and the actions that can be taken next underneath
+pub fn results_view(results: &ResultsModel, config: &AppConfig, frame: &mut Frame) {
+    let show_heatmap = !results.key_stats.is_empty()
+        && frame.area().height >= RESULTS_HEIGHT + KEYBOARD_HEIGHT + 2;
+    let heatmap_height = if show_heatmap { KEYBOARD_HEIGHT } else { 0 };
+
+    let horizontal_center = Layout::horizontal([Constraint::Length(80)]).flex(Flex::Center).split(frame.area())[0];
+    let [banner_area, scores_area, _, chart_area, _, details_area, _, heatmap_title_area, heatmap_area, _, actions_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Length(12),
+        Constraint::Length(1),
+        Constraint::Length(3),
+        Constraint::Length(1),
+        Constraint::Length(heatmap_height.min(1)),
+        Constraint::Length(heatmap_height),
+        Constraint::Length(heatmap_height.min(1)),
+        Constraint::Length(2),
+    ])
+    .flex(Flex::Center)
+    .areas(horizontal_center);
+
+    let result = &results.result;
+    let theme = &config.theme;
+    let keybindings = &config.keybindings;
+    let highlight = Style::new().fg(theme.accent);
+    let label = Style::new().fg(theme.label);
+
+    let banner = match (result.failure, results.is_personal_best(), results.previous_best) {
+        (Some(failure), _, _) => Line::from(vec![
+            Span::styled("failed", Style::new().fg(theme.warning).bold()),
+            Span::styled(format!("   {}", failure), label),
+        ]),
+        (None, false, Some(best)) => Line::from(vec![
+            Span::styled("personal best ", label),
+            Span::raw(format!("{:.1} wpm", best)),
+        ]),
+        (None, true, Some(previous_best)) => Line::from(vec![
+            Span::styled("new personal best!", highlight.bold()),
+            Span::styled(format!("   previous {:.1} wpm", previous_best), label),
+        ]),
+        // there is nothing to compare the first result for a test against
+        (None, _, None) => Line::from(vec![
+            Span::styled("new personal best!", highlight.bold()),
+            Span::styled("   first result for this test", label),
+        ]),
+    };
+
+    let scores = Line::from(vec![
+        Span::styled(format!("{:.1}", result.wpm), highlight),
+        Span::styled(" wpm   ", label),
+        Span::styled(format!("{:.1}%", result.accuracy), highlight),
+        Span::styled(" acc", label),
+    ]);
+
+    let mut details = vec![
+        Line::from(vec![
+            Span::styled("time ", label),
+            Span::raw(format!("{:.1}s", result.duration.as_secs_f32())),
+            Span::styled("   characters ", label),
+            Span::raw(format!(
+                "{} correct / {} incorrect",
+                result.correct_characters, result.incorrect_characters
+            )),
+        ]),
+        Line::from(vec![
+            Span::styled("test ", label),
+            Span::raw(result.settings.to_string()),
+            Span::styled("   completed ", label),
+            Span::raw(result.completed_at.format("%Y-%m-%d %H:%M").to_string()),
+        ]),
+    ];
+    if let Some(quote) = result.quote_id.and_then(Quote::by_id) {
+        details.push(Line::from(vec![
+            Span::styled("source ", label),
+            Span::raw(quote.source.as_str()),
+        ]));
+    }
+
+    let mut actions = vec![
+        format!("{} retry", key_name(keybindings.retry)),
+        format!("{} new test", key_name(keybindings.new_test)),
+    ];
+    if !result.keystrokes.is_empty() {
+        actions.push(format!("{} replay", key_name(keybindings.replay)));
+    }
+    if !result.mistyped_words.is_empty() {
+        actions.push(format!("{} practise missed", key_name(keybindings.practise_missed)));
+    }
+    if show_heatmap {
+        actions.push(format!("{} heatmap", key_name(keybindings.heatmap)));
+    }
+    // going somewhere else goes on a line of its own
+    let navigation = [
+        format!("{} menu", key_name(keybindings.menu)),
+        format!("{} history", key_name(keybindings.history)),
+        format!("{} quit", key_name(keybindings.quit)),
+    ];
+    let actions = Text::from(vec![
+        Line::styled(actions.join(" · "), label),
+        Line::styled(navigation.join(" · "), label),
+    ]);
+
+    let chart = WpmChartWidget {
+        samples: &result.wpm_samples,
+        theme,
+    };
+
+    frame.render_widget(Paragraph::new(banner), banner_area);
+    frame.render_widget(Paragraph::new(scores), scores_area);
+    frame.render_widget(chart, chart_area);
+    frame.render_widget(Paragraph::new(details), details_area);
+    frame.render_widget(Paragraph::new(actions), actions_area);
+
+    if show_heatmap {
+        let heatmap_title = Line::from(vec![
+            Span::styled("keys by ", label),
+            Span::raw(results.heatmap.to_string()),
+        ]);
+        let heatmap = KeyboardHeatmapWidget {
+            key_stats: &results.key_stats,
+            layout: &config.keyboard_layout,
+            mode: results.heatmap,
+            theme,
+        };
+
+        frame.render_widget(Paragraph::new(heatmap_title), heatmap_title_area);
+        frame.render_widget(heatmap, heatmap_area);
+    }
+}
@@ -0,0 +1,120 @@
+use ratatui::{
+    prelude::*,
+    symbols::Marker,
+    widgets::{Axis, Chart, Dataset, GraphType, LegendPosition},
+};
+
+use crate::{config::theme::Theme, features::words_challenge::challenge_result::WpmSample};
+
+// Plots the wpm and raw wpm for each second of a challenge, with a marker on the wpm line at each
+// second where a mistake was made
+pub struct WpmChartWidget<'a> {
+    pub samples: &'a [WpmSample],
+    pub theme: &'a Theme,
+}
+
+impl Widget for WpmChartWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        // sample n covers the nth second so the points start from 1
+        let points = |value: fn(&WpmSample) -> f32| -> Vec<(f64, f64)> {
+            self.samples
+                .iter()
+                .enumerate()
+                .map(|(index, sample)| ((index + 1) as f64, value(sample) as f64))
+                .collect()
+        };
+
+        let wpm_points = points(|sample| sample.wpm);
+        let raw_points = points(|sample| sample.raw_wpm);
+        let error_points: Vec<(f64, f64)> = wpm_points
+            .iter()
+            .zip(self.samples)
+            .filter(|(_, sample)| sample.errors > 0)
+            .map(|(point, _)| *point)
+            .collect();
+
+        let datasets = vec![
+            Dataset::default()
+                .name("raw")
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::new().fg(self.theme.chart_raw))
+                .data(&raw_points),
+            Dataset::default()
+                .name("wpm")
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::new().fg(self.theme.chart_wpm))
+                .data(&wpm_points),
+            Dataset::default()
+                .name("errors")
+                .marker(Marker::Dot)
+                .graph_type(GraphType::Scatter)
+                .style(Style::new().fg(self.theme.chart_errors))
+                .data(&error_points),
+        ];
+
+        let seconds = self.samples.len().max(1) as f64;
+        let y_max = y_axis_max(self.samples);
+        let axis_style = Style::new().fg(self.theme.label);
+
+        let x_axis = Axis::default()
+            .style(axis_style)
+            .bounds([1.0, seconds])
+            .labels(["1".to_string(), format!("{}s", seconds)]);
+        let y_axis = Axis::default()
+            .style(axis_style)
+            .bounds([0.0, y_max])
+            .labels(["0".to_string(), format!("{}", y_max / 2.0), format!("{}", y_max)]);
+
+        Chart::new(datasets)
+            .x_axis(x_axis)
+            .y_axis(y_axis)
+            .legend_position(Some(LegendPosition::TopRight))
+            .hidden_legend_constraints((Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)))
+            .render(area, buf);
+    }
+}
+
+// the top of the chart, the fastest sample rounded up to the next multiple of 20
+fn y_axis_max(samples: &[WpmSample]) -> f64 {
+    let fastest = samples
+        .iter()
+        .map(|sample| sample.wpm.max(sample.raw_wpm))
+        .fold(0.0, f32::max) as f64;
+
+    ((fastest / 20.0).floor() + 1.0) * 20.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(wpm: f32, raw_wpm: f32) -> WpmSample {
+        WpmSample {
+            wpm,
+            raw_wpm,
+            errors: 0,
+        }
+    }
+
+    #[test]
+    fn y_axis_fits_fastest_sample() {
+        let samples = [sample(42.0, 55.0), sample(61.0, 48.0)];
+        assert_eq!(y_axis_max(&samples), 80.0);
+    }
+
+    #[test]
+    fn y_axis_has_room_above_exact_multiple() {
+        let samples = [sample(40.0, 40.0)];
+        assert_eq!(y_axis_max(&samples), 60.0);
+    }
+
+    #[test]
+    fn y_axis_without_samples_is_not_empty() {
+        assert_eq!(y_axis_max(&[]), 20.0);
+    }
+}
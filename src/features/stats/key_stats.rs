@@ -0,0 +1,264 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    time::Duration,
+};
+
+use crate::features::{
+    keyboard::keyboard_layout::Key,
+    words_challenge::keystroke::{timed_presses, Keystroke},
+};
+
+// how many of the latest correct presses of a key are kept to work out its p90 latency from, so
+// a long history doesn't keep every press it ever had
+pub const RECENT_LATENCIES: usize = 500;
+
+// How a key has been typed, from every time it was the one that should have been pressed
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct KeyRecord {
+    pub presses: usize,
+    // presses of some other key when this one should have been pressed
+    pub errors: usize,
+    // how long the correct presses took after the key pressed before each of them, all added up
+    pub latency_total: Duration,
+    pub latency_count: usize,
+    // the latest of those times, oldest first
+    recent_latencies: VecDeque<Duration>,
+}
+
+impl KeyRecord {
+    pub fn new(presses: usize, errors: usize, latency_total: Duration, latency_count: usize) -> Self {
+        Self {
+            presses,
+            errors,
+            latency_total,
+            latency_count,
+            recent_latencies: VecDeque::new(),
+        }
+    }
+
+    // the latest times in order, oldest first, with only the last few kept
+    pub fn with_recent_latencies(mut self, latencies: impl IntoIterator<Item = Duration>) -> Self {
+        for latency in latencies {
+            self.keep_recent_latency(latency);
+        }
+        self
+    }
+
+    pub fn recent_latencies(&self) -> impl Iterator<Item = Duration> + '_ {
+        self.recent_latencies.iter().copied()
+    }
+
+    fn add_latency(&mut self, latency: Duration) {
+        self.latency_total += latency;
+        self.latency_count += 1;
+        self.keep_recent_latency(latency);
+    }
+
+    fn keep_recent_latency(&mut self, latency: Duration) {
+        self.recent_latencies.push_back(latency);
+        if self.recent_latencies.len() > RECENT_LATENCIES {
+            self.recent_latencies.pop_front();
+        }
+    }
+
+    // as a percentage of the presses
+    pub fn error_rate(&self) -> f32 {
+        if self.presses == 0 {
+            return 0.0;
+        }
+
+        self.errors as f32 / self.presses as f32 * 100.0
+    }
+
+    pub fn average_latency(&self) -> Option<Duration> {
+        if self.latency_count == 0 {
+            return None;
+        }
+
+        Some(self.latency_total / self.latency_count as u32)
+    }
+
+    // the time nine in ten of the latest correct presses were quicker than or as quick as
+    pub fn p90_latency(&self) -> Option<Duration> {
+        let mut latencies: Vec<Duration> = self.recent_latencies.iter().copied().collect();
+        latencies.sort();
+
+        let rank = (latencies.len() as f32 * 0.9).ceil() as usize;
+        latencies.get(rank.checked_sub(1)?).copied()
+    }
+
+    fn add(&mut self, other: &KeyRecord) {
+        self.presses += other.presses;
+        self.errors += other.errors;
+        self.latency_total += other.latency_total;
+        self.latency_count += other.latency_count;
+        for latency in other.recent_latencies() {
+            self.keep_recent_latency(latency);
+        }
+    }
+}
+
+// Records for each character that should have been typed, either for a single challenge or
+// added up across every saved result
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct KeyStats {
+    characters: BTreeMap<char, KeyRecord>,
+}
+
+impl KeyStats {
+    pub fn from_keystrokes(keystrokes: &[Keystroke]) -> Self {
+        Self::default().with_keystrokes(keystrokes)
+    }
+
+    // records already added up, as they're stored for every saved result
+    pub fn from_records(characters: BTreeMap<char, KeyRecord>) -> Self {
+        Self { characters }
+    }
+
+    pub fn records(&self) -> impl Iterator<Item = (char, &KeyRecord)> {
+        self.characters.iter().map(|(char, record)| (*char, record))
+    }
+
+    // adds another challenge's keystrokes to the records, each key press counts towards the
+    // character at the caret
+    pub fn with_keystrokes(mut self, keystrokes: &[Keystroke]) -> Self {
+        for press in timed_presses(keystrokes) {
+            let record = self.characters.entry(press.expected).or_default();
+            record.presses += 1;
+
+            if !press.correct {
+                record.errors += 1;
+            }
+            if let Some(latency) = press.latency {
+                record.add_latency(latency);
+            }
+        }
+
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.characters.is_empty()
+    }
+
+    // the records for every character the key types put together
+    pub fn for_key(&self, key: Key) -> KeyRecord {
+        let mut record = KeyRecord::default();
+        for (_, character) in self.characters.iter().filter(|(char, _)| key.types(**char)) {
+            record.add(character);
+        }
+
+        record
+    }
+
+    // the average time taken for a correct press of any key, to compare each key's speed against
+    pub fn average_latency(&self) -> Option<Duration> {
+        let mut record = KeyRecord::default();
+        for character in self.characters.values() {
+            record.add(character);
+        }
+
+        record.average_latency()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::features::words_challenge::keystroke::KeystrokeKey;
+
+    use super::*;
+
+    fn keystroke(typed: char, expected: char, millis: u64) -> Keystroke {
+        Keystroke {
+            key: KeystrokeKey::Character(typed),
+            expected: Some(expected),
+            position: 0,
+            time: Duration::from_millis(millis),
+        }
+    }
+
+    fn record_for(stats: &KeyStats, char: char) -> KeyRecord {
+        stats.for_key(Key {
+            base: char,
+            shifted: char.to_ascii_uppercase(),
+        })
+    }
+
+    #[test]
+    fn presses_and_errors_count_towards_expected_character() {
+        let stats = KeyStats::from_keystrokes(&[
+            keystroke('a', 'a', 0),
+            keystroke('s', 'b', 100),
+            keystroke('b', 'b', 300),
+        ]);
+
+        let b = record_for(&stats, 'b');
+        assert_eq!(b.presses, 2);
+        assert_eq!(b.errors, 1);
+        assert_eq!(b.error_rate(), 50.0);
+        assert_eq!(record_for(&stats, 's').presses, 0);
+    }
+
+    #[test]
+    fn latency_is_time_since_previous_keystroke() {
+        let stats = KeyStats::from_keystrokes(&[
+            keystroke('a', 'a', 0),
+            keystroke('x', 'b', 100),
+            Keystroke {
+                key: KeystrokeKey::Backspace,
+                expected: Some('x'),
+                position: 1,
+                time: Duration::from_millis(250),
+            },
+            keystroke('b', 'b', 400),
+        ]);
+
+        assert_eq!(record_for(&stats, 'a').average_latency(), None);
+        assert_eq!(
+            record_for(&stats, 'b').average_latency(),
+            Some(Duration::from_millis(150))
+        );
+    }
+
+    #[test]
+    fn p90_latency_is_slowest_of_fastest_nine_in_ten() {
+        let keystrokes: Vec<Keystroke> = (0..=10)
+            .map(|index| keystroke('a', 'a', index * index * 10))
+            .collect();
+        let stats = KeyStats::from_keystrokes(&keystrokes);
+
+        // the gaps are 10ms, 30ms, 50ms ... 190ms
+        let a = record_for(&stats, 'a');
+        assert_eq!(a.average_latency(), Some(Duration::from_millis(100)));
+        assert_eq!(a.p90_latency(), Some(Duration::from_millis(170)));
+    }
+
+    #[test]
+    fn key_puts_its_shifted_character_with_it() {
+        let stats = KeyStats::from_keystrokes(&[keystroke('a', 'a', 0), keystroke('A', 'A', 100)]);
+
+        assert_eq!(record_for(&stats, 'a').presses, 2);
+    }
+
+    #[test]
+    fn stats_add_up_across_challenges() {
+        let stats = KeyStats::from_keystrokes(&[keystroke('a', 'a', 0)])
+            .with_keystrokes(&[keystroke('x', 'a', 0)]);
+
+        let a = record_for(&stats, 'a');
+        assert_eq!(a.presses, 2);
+        assert_eq!(a.errors, 1);
+    }
+
+    #[test]
+    fn only_the_latest_latencies_are_kept_for_the_p90() {
+        let keystrokes: Vec<Keystroke> = (0..=RECENT_LATENCIES as u64 + 10)
+            .map(|index| keystroke('a', 'a', index * 100))
+            .collect();
+        let a = record_for(&KeyStats::from_keystrokes(&keystrokes), 'a');
+
+        assert_eq!(a.latency_count, RECENT_LATENCIES + 10);
+        assert_eq!(a.recent_latencies().count(), RECENT_LATENCIES);
+        assert_eq!(a.p90_latency(), Some(Duration::from_millis(100)));
+    }
+}
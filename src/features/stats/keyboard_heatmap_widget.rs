@@ -0,0 +1,183 @@
+use std::{fmt, time::Duration};
+
+use ratatui::prelude::*;
+
+use crate::{
+    config::theme::Theme,
+    features::keyboard::{
+        keyboard_layout::{KeyboardKey, KeyboardLayout, SPACE},
+        keyboard_widget::KeyboardWidget,
+    },
+};
+
+use super::key_stats::{KeyRecord, KeyStats};
+
+// error rates, as percentages, up to which a key is good or fair
+const GOOD_ERROR_RATE: f32 = 2.0;
+const FAIR_ERROR_RATE: f32 = 5.0;
+// how many times slower than the average key a key can be and still be good or fair
+const GOOD_SLOWNESS: f32 = 1.0;
+const FAIR_SLOWNESS: f32 = 1.25;
+
+// What the keys on the heatmap are coloured by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HeatmapMode {
+    #[default]
+    ErrorRate,
+    Speed,
+}
+
+impl HeatmapMode {
+    pub fn next(self) -> Self {
+        match self {
+            HeatmapMode::ErrorRate => HeatmapMode::Speed,
+            HeatmapMode::Speed => HeatmapMode::ErrorRate,
+        }
+    }
+
+    // how well the key is typed, nothing when it hasn't been pressed enough to say
+    pub fn rate(self, record: &KeyRecord, average_latency: Option<Duration>) -> Option<Rating> {
+        let (value, good, fair) = match self {
+            HeatmapMode::ErrorRate if record.presses > 0 => {
+                (record.error_rate(), GOOD_ERROR_RATE, FAIR_ERROR_RATE)
+            }
+            HeatmapMode::Speed => {
+                let slowness = record.average_latency()?.as_secs_f32()
+                    / average_latency?.as_secs_f32().max(f32::EPSILON);
+                (slowness, GOOD_SLOWNESS, FAIR_SLOWNESS)
+            }
+            _ => return None,
+        };
+
+        let rating = if value <= good {
+            Rating::Good
+        } else if value <= fair {
+            Rating::Fair
+        } else {
+            Rating::Poor
+        };
+
+        Some(rating)
+    }
+}
+
+impl fmt::Display for HeatmapMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HeatmapMode::ErrorRate => write!(f, "error rate"),
+            HeatmapMode::Speed => write!(f, "speed"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rating {
+    Good,
+    Fair,
+    Poor,
+}
+
+// Draws the keyboard with each key coloured by how well it's typed, keys that haven't been
+// pressed are left uncoloured
+pub struct KeyboardHeatmapWidget<'a> {
+    pub key_stats: &'a KeyStats,
+    pub layout: &'a KeyboardLayout,
+    pub mode: HeatmapMode,
+    pub theme: &'a Theme,
+}
+
+impl KeyboardHeatmapWidget<'_> {
+    fn key_style(&self, record: &KeyRecord, average_latency: Option<Duration>) -> Style {
+        let background = match self.mode.rate(record, average_latency) {
+            Some(Rating::Good) => self.theme.heatmap_good,
+            Some(Rating::Fair) => self.theme.heatmap_fair,
+            Some(Rating::Poor) => self.theme.heatmap_poor,
+            None => return Style::new().fg(self.theme.label),
+        };
+
+        Style::new().fg(self.theme.key_text).bg(background)
+    }
+}
+
+impl Widget for KeyboardHeatmapWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let average_latency = self.key_stats.average_latency();
+        let key_style = |key| {
+            let record = match key {
+                KeyboardKey::Typing { key, .. } => self.key_stats.for_key(key),
+                KeyboardKey::Space => self.key_stats.for_key(SPACE),
+                KeyboardKey::Shift(_) => KeyRecord::default(),
+            };
+            self.key_style(&record, average_latency)
+        };
+
+        KeyboardWidget {
+            layout: self.layout,
+            show_shift: false,
+            key_style,
+        }
+        .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::features::words_challenge::keystroke::{Keystroke, KeystrokeKey};
+
+    use super::*;
+
+    // the record for a pressed the given number of times with the first few of them wrong, the
+    // presses are 100ms apart apart from the gap before the last one
+    fn record(presses: usize, errors: usize, last_gap: u64) -> KeyRecord {
+        let mut time = 0;
+        let keystrokes: Vec<Keystroke> = (0..presses)
+            .map(|index| {
+                time += if index + 1 == presses { last_gap } else { 100 };
+                Keystroke {
+                    key: KeystrokeKey::Character(if index < errors { 'x' } else { 'a' }),
+                    expected: Some('a'),
+                    position: index,
+                    time: Duration::from_millis(time),
+                }
+            })
+            .collect();
+
+        let a = KeyboardLayout::qwerty().rows()[2][0];
+        KeyStats::from_keystrokes(&keystrokes).for_key(a)
+    }
+
+    #[test]
+    fn error_rate_ratings_get_worse_with_more_errors() {
+        let mode = HeatmapMode::ErrorRate;
+
+        assert_eq!(mode.rate(&record(100, 1, 100), None), Some(Rating::Good));
+        assert_eq!(mode.rate(&record(100, 4, 100), None), Some(Rating::Fair));
+        assert_eq!(mode.rate(&record(100, 10, 100), None), Some(Rating::Poor));
+    }
+
+    #[test]
+    fn speed_ratings_compare_against_average_key() {
+        let mode = HeatmapMode::Speed;
+        let average = Some(Duration::from_millis(100));
+
+        assert_eq!(mode.rate(&record(5, 0, 100), average), Some(Rating::Good));
+        assert_eq!(mode.rate(&record(5, 0, 150), average), Some(Rating::Fair));
+        assert_eq!(mode.rate(&record(5, 0, 500), average), Some(Rating::Poor));
+    }
+
+    #[test]
+    fn keys_never_pressed_have_no_rating() {
+        let average = Some(Duration::from_millis(200));
+
+        assert_eq!(HeatmapMode::ErrorRate.rate(&record(0, 0, 0), average), None);
+        assert_eq!(HeatmapMode::Speed.rate(&record(0, 0, 0), average), None);
+    }
+
+    #[test]
+    fn switching_mode_goes_back_round() {
+        assert_eq!(HeatmapMode::ErrorRate.next().next(), HeatmapMode::ErrorRate);
+    }
+}
@@ -0,0 +1,7 @@
+pub(crate) mod key_stats;
+pub(crate) mod keyboard_heatmap_widget;
+pub(crate) mod stats_model;
+pub(crate) mod stats_report;
+pub(crate) mod stats_summary;
+pub(crate) mod stats_update;
+pub(crate) mod stats_view;
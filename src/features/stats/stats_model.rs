@@ -0,0 +1,122 @@
+use color_eyre::Result;
+use crossterm::event::KeyEvent;
+
+use crate::{
+    config::keybindings::Keybindings,
+    features::keyboard::keyboard_layout::{Key, KeyboardLayout, SPACE},
+    tui::message::Message,
+};
+
+use super::{
+    key_stats::{KeyRecord, KeyStats},
+    keyboard_heatmap_widget::HeatmapMode,
+    stats_summary::StatsSummary,
+};
+
+// Totals across every saved result along with how each key has been typed in all of them
+#[derive(Debug)]
+pub struct StatsModel {
+    pub(super) summary: Option<StatsSummary>,
+    pub(super) key_stats: KeyStats,
+    pub(super) heatmap: HeatmapMode,
+}
+
+impl StatsModel {
+    pub fn new(summary: Option<StatsSummary>, key_stats: KeyStats) -> Self {
+        Self {
+            summary,
+            key_stats,
+            heatmap: HeatmapMode::default(),
+        }
+    }
+
+    pub(super) fn switch_heatmap(self) -> Self {
+        Self {
+            heatmap: self.heatmap.next(),
+            ..self
+        }
+    }
+
+    // the keys of the layout that have been pressed, worst first by whatever the heatmap is showing
+    pub(super) fn worst_keys(&self, layout: &KeyboardLayout) -> Vec<(Key, KeyRecord)> {
+        let mut keys: Vec<(Key, KeyRecord)> = layout
+            .keys()
+            .chain([SPACE])
+            .map(|key| (key, self.key_stats.for_key(key)))
+            .filter(|(_, record)| record.presses > 0)
+            .collect();
+
+        keys.sort_by(|(_, a), (_, b)| match self.heatmap {
+            HeatmapMode::ErrorRate => b.error_rate().total_cmp(&a.error_rate()),
+            HeatmapMode::Speed => match (a.average_latency(), b.average_latency()) {
+                (Some(a), Some(b)) => b.cmp(&a),
+                // keys only ever pressed wrongly have no speed and go last
+                (a, b) => b.is_some().cmp(&a.is_some()),
+            },
+        });
+
+        keys
+    }
+
+    pub fn handle_event(
+        &self,
+        key_event: KeyEvent,
+        keybindings: &Keybindings,
+    ) -> Result<Option<Message>> {
+        let message = match key_event.code {
+            key if key == keybindings.heatmap => Some(Message::SwitchHeatmap),
+            key if key == keybindings.menu => Some(Message::OpenMenu),
+            key if key == keybindings.history => Some(Message::OpenHistory),
+            _ => None,
+        };
+
+        Ok(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::features::words_challenge::keystroke::{Keystroke, KeystrokeKey};
+
+    use super::*;
+
+    fn keystroke(typed: char, expected: char, millis: u64) -> Keystroke {
+        Keystroke {
+            key: KeystrokeKey::Character(typed),
+            expected: Some(expected),
+            position: 0,
+            time: Duration::from_millis(millis),
+        }
+    }
+
+    // b is always typed wrong and c is slower than a
+    fn stats() -> StatsModel {
+        let key_stats = KeyStats::from_keystrokes(&[
+            keystroke('a', 'a', 0),
+            keystroke('a', 'a', 100),
+            keystroke('x', 'b', 200),
+            keystroke('c', 'c', 500),
+        ]);
+
+        StatsModel::new(None, key_stats)
+    }
+
+    fn worst_key_names(stats: &StatsModel) -> Vec<char> {
+        stats.worst_keys(&KeyboardLayout::qwerty()).iter().map(|(key, _)| key.base).collect()
+    }
+
+    #[test]
+    fn worst_keys_by_error_rate_have_most_errors_first() {
+        assert_eq!(worst_key_names(&stats())[0], 'b');
+    }
+
+    #[test]
+    fn worst_keys_by_speed_are_slowest_first() {
+        let stats = stats().switch_heatmap();
+
+        assert_eq!(stats.heatmap, HeatmapMode::Speed);
+        assert_eq!(worst_key_names(&stats), vec!['c', 'a', 'b']);
+    }
+}
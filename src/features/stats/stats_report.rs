@@ -0,0 +1,79 @@
+use std::{
+    io::{self, Write},
+    time::Duration,
+};
+
+use crate::features::keyboard::keyboard_layout::{KeyboardLayout, SPACE};
+
+use super::{key_stats::KeyStats, stats_summary::StatsSummary};
+
+// Prints the summary as plain text for `typee stats`, followed by each key that's been pressed
+// in the order they are on the layout
+pub fn write_stats(
+    stats: Option<&StatsSummary>,
+    key_stats: &KeyStats,
+    layout: &KeyboardLayout,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    let Some(stats) = stats else {
+        return writeln!(out, "no results yet");
+    };
+
+    let seconds_typing = stats.time_typing.as_secs();
+    writeln!(out, "tests completed  {}", stats.tests_completed)?;
+    writeln!(
+        out,
+        "time typing      {}h {:02}m {:02}s",
+        seconds_typing / 3600,
+        seconds_typing / 60 % 60,
+        seconds_typing % 60
+    )?;
+    writeln!(out, "average wpm      {:.1}", stats.average_wpm)?;
+    writeln!(out, "recent wpm       {:.1}", stats.recent_average_wpm)?;
+    writeln!(out, "average accuracy {:.1}%", stats.average_accuracy)?;
+
+    writeln!(out)?;
+    writeln!(out, "personal bests               wpm     acc  date")?;
+    for best in &stats.personal_bests {
+        writeln!(
+            out,
+            "{:<24}{:>8.1}{:>7.1}%  {}",
+            best.settings.to_string(),
+            best.wpm,
+            best.accuracy,
+            best.completed_at.format("%Y-%m-%d")
+        )?;
+    }
+
+    if key_stats.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(out)?;
+    writeln!(out, "keys      presses  errors average     p90")?;
+    for key in layout.keys().chain([SPACE]) {
+        let record = key_stats.for_key(key);
+        if record.presses == 0 {
+            continue;
+        }
+
+        writeln!(
+            out,
+            "{:<8}{:>9}{:>7.1}%{:>8}{:>8}",
+            key.name(),
+            record.presses,
+            record.error_rate(),
+            latency(record.average_latency()),
+            latency(record.p90_latency())
+        )?;
+    }
+
+    Ok(())
+}
+
+fn latency(latency: Option<Duration>) -> String {
+    match latency {
+        Some(latency) => format!("{}ms", latency.as_millis()),
+        None => "-".to_string(),
+    }
+}
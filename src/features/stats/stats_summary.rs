@@ -0,0 +1,141 @@
+use std::time::Duration;
+
+use crate::features::history::result_summary::ResultSummary;
+
+// how many of the newest results the recent average is taken over
+const RECENT_RESULTS: usize = 10;
+
+// Totals and bests across every saved result
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatsSummary {
+    pub tests_completed: usize,
+    pub time_typing: Duration,
+    pub average_wpm: f32,
+    pub average_accuracy: f32,
+    pub recent_average_wpm: f32,
+    // the fastest result for each test that wasn't failed, the most recently done tests first
+    pub personal_bests: Vec<ResultSummary>,
+}
+
+impl StatsSummary {
+    // the results should be newest first, as the history loads them by default, there's nothing
+    // to summarise without any
+    pub fn from_results(results: &[ResultSummary]) -> Option<Self> {
+        if results.is_empty() {
+            return None;
+        }
+
+        let mut personal_bests: Vec<ResultSummary> = Vec::new();
+        for result in results.iter().filter(|result| !result.failed) {
+            match personal_bests.iter_mut().find(|best| best.settings == result.settings) {
+                Some(best) if result.wpm > best.wpm => *best = result.clone(),
+                Some(_) => {}
+                None => personal_bests.push(result.clone()),
+            }
+        }
+
+        let recent_results = &results[..results.len().min(RECENT_RESULTS)];
+
+        Some(Self {
+            tests_completed: results.len(),
+            time_typing: results.iter().map(|result| result.duration).sum(),
+            average_wpm: average(results, |result| result.wpm),
+            average_accuracy: average(results, |result| result.accuracy),
+            recent_average_wpm: average(recent_results, |result| result.wpm),
+            personal_bests,
+        })
+    }
+}
+
+fn average(results: &[ResultSummary], value: impl Fn(&ResultSummary) -> f32) -> f32 {
+    results.iter().map(value).sum::<f32>() / results.len() as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Local, TimeZone};
+
+    use crate::features::words_challenge::{
+        challenge_mode::ChallengeMode, challenge_settings::ChallengeSettings,
+        word_generator::WordBand,
+    };
+
+    use super::*;
+
+    fn summary(id: i64, mode: ChallengeMode, wpm: f32) -> ResultSummary {
+        ResultSummary {
+            id,
+            settings: ChallengeSettings::new(mode, WordBand::Top200),
+            language: "english".to_string(),
+            wpm,
+            accuracy: 95.0,
+            duration: Duration::from_secs(30),
+            completed_at: Local.timestamp_opt(1_700_000_000 + id, 0).unwrap(),
+            failed: false,
+        }
+    }
+
+    #[test]
+    fn no_results_have_no_summary() {
+        assert_eq!(StatsSummary::from_results(&[]), None);
+    }
+
+    #[test]
+    fn totals_and_averages_cover_every_result() {
+        let results = [
+            summary(2, ChallengeMode::Words(25), 80.0),
+            summary(1, ChallengeMode::Time(30), 60.0),
+        ];
+
+        let stats = StatsSummary::from_results(&results).unwrap();
+
+        assert_eq!(stats.tests_completed, 2);
+        assert_eq!(stats.time_typing, Duration::from_secs(60));
+        assert_eq!(stats.average_wpm, 70.0);
+        assert_eq!(stats.average_accuracy, 95.0);
+    }
+
+    #[test]
+    fn recent_average_only_covers_newest_results() {
+        let mut results: Vec<ResultSummary> = (0..RECENT_RESULTS as i64)
+            .map(|id| summary(100 - id, ChallengeMode::Words(25), 90.0))
+            .collect();
+        results.push(summary(1, ChallengeMode::Words(25), 10.0));
+
+        let stats = StatsSummary::from_results(&results).unwrap();
+
+        assert_eq!(stats.recent_average_wpm, 90.0);
+        assert!(stats.average_wpm < 90.0);
+    }
+
+    #[test]
+    fn personal_bests_are_fastest_result_per_test() {
+        let results = [
+            summary(4, ChallengeMode::Time(30), 70.0),
+            summary(3, ChallengeMode::Words(25), 65.0),
+            summary(2, ChallengeMode::Words(25), 85.0),
+            summary(1, ChallengeMode::Time(30), 50.0),
+        ];
+
+        let stats = StatsSummary::from_results(&results).unwrap();
+
+        let bests: Vec<i64> = stats.personal_bests.iter().map(|best| best.id).collect();
+        assert_eq!(bests, vec![4, 2]);
+    }
+
+    #[test]
+    fn failed_results_are_never_personal_bests() {
+        let results = [
+            ResultSummary {
+                failed: true,
+                ..summary(2, ChallengeMode::Words(25), 90.0)
+            },
+            summary(1, ChallengeMode::Words(25), 60.0),
+        ];
+
+        let stats = StatsSummary::from_results(&results).unwrap();
+
+        let bests: Vec<i64> = stats.personal_bests.iter().map(|best| best.id).collect();
+        assert_eq!(bests, vec![1]);
+    }
+}
@@ -0,0 +1,25 @@
+use crate::tui::{app_model::AppModel, app_page::AppPage, message::Message};
+use color_eyre::Result;
+
+pub fn stats_update(model: AppModel, message: Message) -> Result<(AppModel, Option<Message>)> {
+    let stats = match model.app_page {
+        AppPage::Stats(stats) => stats,
+        _ => panic!(
+            "stats cannot use non stats models, found: {:?}",
+            model.app_page
+        ),
+    };
+
+    let stats = match message {
+        Message::SwitchHeatmap => stats.switch_heatmap(),
+        _ => stats,
+    };
+
+    Ok((
+        AppModel {
+            app_page: AppPage::Stats(stats),
+            ..model
+        },
+        None,
+    ))
+}
@@ -0,0 +1,122 @@
+use std::time::Duration;
+
+use ratatui::layout::Flex;
+use ratatui::prelude::*;
+use ratatui::widgets::{Paragraph, Row, Table};
+use ratatui::Frame;
+
+use crate::config::{app_config::AppConfig, keybindings::key_name};
+
+use super::keyboard_heatmap_widget::KeyboardHeatmapWidget;
+use crate::features::keyboard::keyboard_widget::KEYBOARD_HEIGHT;
+use super::stats_model::StatsModel;
+
+// Shows the totals across every result above the keyboard heatmap, with the worst keys listed
+// underneath it
+pub fn stats_view(stats: &StatsModel, config: &AppConfig, frame: &mut Frame) {
+    let horizontal_center = Layout::horizontal([Constraint::Length(80)]).flex(Flex::Center).split(frame.area())[0];
+    let [title_area, summary_area, _, heatmap_title_area, heatmap_area, _, table_area, _, help_area] =
+        Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(KEYBOARD_HEIGHT),
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(horizontal_center.inner(Margin::new(0, 1)));
+
+    let theme = &config.theme;
+    let keybindings = &config.keybindings;
+    let highlight = Style::new().fg(theme.accent);
+    let label = Style::new().fg(theme.label);
+
+    let help = Line::styled(
+        format!(
+            "{} heatmap · {} history · {} menu · {} quit",
+            key_name(keybindings.heatmap),
+            key_name(keybindings.history),
+            key_name(keybindings.menu),
+            key_name(keybindings.quit)
+        ),
+        label,
+    );
+
+    frame.render_widget(Paragraph::new("stats").style(highlight.bold()), title_area);
+    frame.render_widget(Paragraph::new(help), help_area);
+
+    let Some(summary) = &stats.summary else {
+        frame.render_widget(Paragraph::new("no results yet").style(label), summary_area);
+        return;
+    };
+
+    let seconds_typing = summary.time_typing.as_secs();
+    let summary_line = Line::from(vec![
+        Span::styled("tests ", label),
+        Span::raw(summary.tests_completed.to_string()),
+        Span::styled("   time ", label),
+        Span::raw(format!(
+            "{}h {:02}m {:02}s",
+            seconds_typing / 3600,
+            seconds_typing / 60 % 60,
+            seconds_typing % 60
+        )),
+        Span::styled("   wpm ", label),
+        Span::raw(format!("{:.1}", summary.average_wpm)),
+        Span::styled("   recent ", label),
+        Span::raw(format!("{:.1}", summary.recent_average_wpm)),
+        Span::styled("   acc ", label),
+        Span::raw(format!("{:.1}%", summary.average_accuracy)),
+    ]);
+    frame.render_widget(Paragraph::new(summary_line), summary_area);
+
+    if stats.key_stats.is_empty() {
+        frame.render_widget(
+            Paragraph::new("no keystrokes recorded yet").style(label),
+            heatmap_title_area,
+        );
+        return;
+    }
+
+    let heatmap_title = Line::from(vec![
+        Span::styled("keys by ", label),
+        Span::raw(stats.heatmap.to_string()),
+    ]);
+    let heatmap = KeyboardHeatmapWidget {
+        key_stats: &stats.key_stats,
+        layout: &config.keyboard_layout,
+        mode: stats.heatmap,
+        theme,
+    };
+
+    let rows = stats
+        .worst_keys(&config.keyboard_layout)
+        .into_iter()
+        .take(table_area.height.saturating_sub(1) as usize)
+        .map(|(key, record)| {
+            Row::new(vec![
+                key.name(),
+                record.presses.to_string(),
+                format!("{:.1}%", record.error_rate()),
+                latency(record.average_latency()),
+                latency(record.p90_latency()),
+            ])
+        });
+    let table = Table::new(rows, [Constraint::Length(7); 5])
+        .header(Row::new(vec!["key", "presses", "errors", "average", "p90"]).style(label))
+        .style(Style::new().fg(theme.secondary));
+
+    frame.render_widget(Paragraph::new(heatmap_title), heatmap_title_area);
+    frame.render_widget(heatmap, heatmap_area);
+    frame.render_widget(table, table_area);
+}
+
+fn latency(latency: Option<Duration>) -> String {
+    match latency {
+        Some(latency) => format!("{}ms", latency.as_millis()),
+        None => "-".to_string(),
+    }
+}
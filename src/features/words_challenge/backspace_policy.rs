@@ -0,0 +1,13 @@
+use serde::Deserialize;
+
+// How far back a mistake can be fixed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackspacePolicy {
+    #[default]
+    Always,
+    // only within the word being typed, a finished word can't be gone back into
+    WithinWord,
+    // every key press is final
+    Never,
+}
@@ -0,0 +1,13 @@
+use serde::Deserialize;
+
+// How the next character to type is picked out
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaretStyle {
+    // a highlighted cell behind the character
+    #[default]
+    Block,
+    Underline,
+    // only the colour of the character itself
+    None,
+}
@@ -0,0 +1,99 @@
+use ratatui::{prelude::*, widgets::Paragraph};
+
+use crate::config::theme::Theme;
+
+use super::{challenge_mode::ChallengeMode, words_challenge_model::WordsChallengeModel};
+
+// Shows how far through the challenge the user is, either the seconds left or the words typed,
+// alongside the latest wpm sample and the letters an adaptive lesson is practising
+pub struct ChallengeHudWidget<'a> {
+    pub challenge_model: &'a WordsChallengeModel,
+    pub theme: &'a Theme,
+}
+
+impl Widget for ChallengeHudWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let progress = match self.challenge_model.mode() {
+            ChallengeMode::Time(_) => {
+                let remaining = self.challenge_model.remaining_time().unwrap_or_default();
+                format!("{}", remaining.as_secs_f32().ceil() as u64)
+            }
+            ChallengeMode::Words(_)
+            | ChallengeMode::Quote(_)
+            | ChallengeMode::Custom(_)
+            | ChallengeMode::Adaptive(_) => {
+                format!(
+                    "{}/{}",
+                    self.challenge_model.typed_word_count(),
+                    self.challenge_model.text_word_count
+                )
+            }
+        };
+
+        let wpm = match self.challenge_model.latest_wpm() {
+            Some(wpm) => format!("{:.0} wpm", wpm),
+            None => String::new(),
+        };
+
+        let ghost = match self.challenge_model.ghost_lead() {
+            Some(lead) => {
+                let color = match lead {
+                    0 => self.theme.label,
+                    lead if lead > 0 => self.theme.ahead,
+                    _ => self.theme.behind,
+                };
+                Span::styled(ghost_lead_text(lead), Style::new().fg(color))
+            }
+            None => Span::raw(""),
+        };
+
+        let mut hud_spans = vec![
+            Span::styled(progress, Style::new().fg(self.theme.hud)),
+            Span::raw("  "),
+            Span::styled(wpm, Style::new().fg(self.theme.label)),
+            Span::raw("  "),
+            ghost,
+        ];
+
+        // the letter being focused on stands out from the rest
+        if let Some(letters) = self.challenge_model.adaptive_letters() {
+            hud_spans.push(Span::raw("  "));
+            for letter in &letters.unlocked {
+                let style = if *letter == letters.focus {
+                    Style::new().fg(self.theme.accent).bold()
+                } else {
+                    Style::new().fg(self.theme.label)
+                };
+                hud_spans.push(Span::styled(format!("{} ", letter), style));
+            }
+        }
+
+        let hud_line = Line::from(hud_spans);
+
+        Paragraph::new(hud_line).render(area, buf)
+    }
+}
+
+// how far ahead of or behind the ghost the caret is, in characters
+fn ghost_lead_text(lead: i64) -> String {
+    match lead {
+        0 => "level".to_string(),
+        lead if lead > 0 => format!("+{} ahead", lead),
+        lead => format!("{} behind", lead.unsigned_abs()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ghost_lead_says_how_far_ahead_or_behind() {
+        assert_eq!(ghost_lead_text(3), "+3 ahead");
+        assert_eq!(ghost_lead_text(0), "level");
+        assert_eq!(ghost_lead_text(-3), "3 behind");
+    }
+}
@@ -0,0 +1,46 @@
+use std::{fmt, time::Duration};
+
+use super::quote::QuoteLength;
+
+// What ends a challenge
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChallengeMode {
+    // type a fixed number of words
+    Words(usize),
+    // type as much as possible before the given number of seconds runs out
+    Time(u64),
+    // type a quote of about the given length
+    Quote(QuoteLength),
+    // type text given on the command line, which has the given number of words
+    Custom(usize),
+    // type the given number of made up words practising the letters that need it most
+    Adaptive(usize),
+}
+
+impl ChallengeMode {
+    pub const WORD_COUNTS: [usize; 4] = [10, 25, 50, 100];
+    pub const TIME_LIMITS: [u64; 4] = [15, 30, 60, 120];
+    pub const ADAPTIVE_WORD_COUNT: usize = 20;
+
+    pub fn time_limit(&self) -> Option<Duration> {
+        match self {
+            ChallengeMode::Words(_)
+            | ChallengeMode::Quote(_)
+            | ChallengeMode::Custom(_)
+            | ChallengeMode::Adaptive(_) => None,
+            ChallengeMode::Time(seconds) => Some(Duration::from_secs(*seconds)),
+        }
+    }
+}
+
+impl fmt::Display for ChallengeMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChallengeMode::Words(num_words) => write!(f, "words {}", num_words),
+            ChallengeMode::Time(seconds) => write!(f, "time {}", seconds),
+            ChallengeMode::Quote(length) => write!(f, "quote {}", length),
+            ChallengeMode::Custom(num_words) => write!(f, "custom {}", num_words),
+            ChallengeMode::Adaptive(num_words) => write!(f, "adaptive {}", num_words),
+        }
+    }
+}
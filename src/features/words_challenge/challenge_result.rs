@@ -0,0 +1,62 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    challenge_settings::ChallengeSettings, fail_conditions::FailReason, keystroke::Keystroke,
+    stop_on_error::StopOnError,
+};
+
+// how many times each mistyped word is typed when practising them
+const PRACTICE_REPEATS: usize = 3;
+
+// The outcome of a finished challenge
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChallengeResult {
+    pub settings: ChallengeSettings,
+    pub seed: u64,
+    pub wpm: f32,
+    pub accuracy: f32,
+    pub duration: Duration,
+    pub correct_characters: usize,
+    pub incorrect_characters: usize,
+    pub completed_at: DateTime<Local>,
+    // one sample for every second of the challenge
+    pub wpm_samples: Vec<WpmSample>,
+    pub keystrokes: Vec<Keystroke>,
+    // the quote that was typed in quote mode
+    pub quote_id: Option<u32>,
+    // the text that was typed in custom mode or an adaptive lesson, as it can't be generated again
+    pub custom_text: Option<String>,
+    // replays need to stop on the same mistakes the run did
+    pub stop_on_error: StopOnError,
+    // why the challenge was failed, failed results never count as personal bests
+    pub failure: Option<FailReason>,
+    // every word with a mistake in it, once each in the order they come in the text, even if the
+    // mistake was put right
+    pub mistyped_words: Vec<String>,
+}
+
+impl ChallengeResult {
+    // the mistyped words each a few times over, nothing when there weren't any
+    pub fn practice_text(&self) -> Option<String> {
+        if self.mistyped_words.is_empty() {
+            return None;
+        }
+
+        let words: Vec<&str> = self
+            .mistyped_words
+            .iter()
+            .flat_map(|word| [word.as_str(); PRACTICE_REPEATS])
+            .collect();
+        Some(words.join(" "))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WpmSample {
+    pub wpm: f32,
+    pub raw_wpm: f32,
+    pub errors: usize,
+}
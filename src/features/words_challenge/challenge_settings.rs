@@ -0,0 +1,60 @@
+use std::fmt;
+
+use super::{challenge_mode::ChallengeMode, word_generator::WordBand};
+
+// Everything needed to set up a challenge apart from the seed, so the same settings can be used
+// to retry a challenge or start a fresh one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChallengeSettings {
+    pub mode: ChallengeMode,
+    pub word_band: WordBand,
+}
+
+impl ChallengeSettings {
+    pub fn new(mode: ChallengeMode, word_band: WordBand) -> Self {
+        // quotes, custom text and adaptive lessons don't come from the word lists, so they all get
+        // the same band to make sure their results are only ever told apart by their length
+        let word_band = match mode {
+            ChallengeMode::Quote(_) | ChallengeMode::Custom(_) | ChallengeMode::Adaptive(_) => {
+                WordBand::Top200
+            }
+            ChallengeMode::Words(_) | ChallengeMode::Time(_) => word_band,
+        };
+
+        Self { mode, word_band }
+    }
+}
+
+impl fmt::Display for ChallengeSettings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.mode {
+            ChallengeMode::Quote(_) | ChallengeMode::Custom(_) | ChallengeMode::Adaptive(_) => {
+                write!(f, "{}", self.mode)
+            }
+            ChallengeMode::Words(_) | ChallengeMode::Time(_) => {
+                write!(f, "{} · {}", self.mode, self.word_band)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::features::words_challenge::quote::QuoteLength;
+
+    use super::*;
+
+    #[test]
+    fn quote_settings_ignore_word_band() {
+        let first = ChallengeSettings::new(ChallengeMode::Quote(QuoteLength::Short), WordBand::Top1k);
+        let second = ChallengeSettings::new(ChallengeMode::Quote(QuoteLength::Short), WordBand::Top10k);
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn word_settings_keep_word_band() {
+        let settings = ChallengeSettings::new(ChallengeMode::Words(25), WordBand::Top1k);
+        assert_eq!(settings.word_band, WordBand::Top1k);
+    }
+}
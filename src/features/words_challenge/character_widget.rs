@@ -1,52 +1,96 @@
 use ratatui::{
     buffer::Buffer,
-    style::{palette::tailwind, Style},
+    style::{Modifier, Style},
     widgets::{Paragraph, Widget},
 };
 
-use super::words_challenge_model::WordsChallengeModel;
+use crate::config::theme::Theme;
 
-enum CharacterStyle {
+use super::{caret_style::CaretStyle, words_challenge_model::WordsChallengeModel};
+
+pub enum CharacterStyle {
+    // the character to type next
+    Caret(CaretStyle),
+    // the caret after a wrong key when stopping on errors, drawn as a block whatever the caret style
+    Stopped,
     NextCharacter,
     Correct,
     Incorrect,
+    // typed past the end of a word
+    Extra,
+    // skipped over by pressing space partway through a word
+    Missed,
+    // where the ghost of an earlier run has got to
+    Ghost,
 }
 
-pub struct CharacterWidget {
+pub struct CharacterWidget<'a> {
     pub char: char,
     pub style: CharacterStyle,
+    pub theme: &'a Theme,
 }
 
-impl CharacterWidget {
-    pub fn get_widget_from_model(model: &WordsChallengeModel, index: usize) -> Self {
+impl<'a> CharacterWidget<'a> {
+    pub fn get_widget_from_model(
+        model: &WordsChallengeModel,
+        index: usize,
+        caret_style: CaretStyle,
+        theme: &'a Theme,
+    ) -> Self {
         let character = model.text.chars().nth(index).unwrap();
         let is_typed = model.current_pos > index;
         let is_incorrect = model.incorrect_indices.contains(&index);
+        let is_missed = model.missed_indices.contains(&index);
+        let is_caret = model.current_pos == index;
+        let is_stopped = is_caret && model.uncorrected_indices.contains(&index);
+        let is_ghost = model.ghost_position() == Some(index) && !is_caret;
 
-        let character_style = match (is_typed, is_incorrect) {
-            (true, true) => CharacterStyle::Incorrect,
-            (true, false) => CharacterStyle::Correct,
-            (false, _) => CharacterStyle::NextCharacter,
+        let character_style = match (is_ghost, is_typed, is_incorrect) {
+            (true, _, _) => CharacterStyle::Ghost,
+            (false, false, _) if is_stopped => CharacterStyle::Stopped,
+            (false, false, _) if is_caret => CharacterStyle::Caret(caret_style),
+            (false, true, _) if is_missed => CharacterStyle::Missed,
+            (false, true, true) => CharacterStyle::Incorrect,
+            (false, true, false) => CharacterStyle::Correct,
+            (false, false, _) => CharacterStyle::NextCharacter,
         };
 
         CharacterWidget {
             char: character,
             style: character_style,
+            theme,
         }
     }
 }
 
-impl Widget for CharacterWidget {
+impl Widget for CharacterWidget<'_> {
     fn render(self, area: ratatui::layout::Rect, buf: &mut Buffer)
     where
         Self: Sized,
     {
+        let theme = self.theme;
+        let next_character = Style::new().fg(theme.untyped).bg(theme.untyped_background);
+
         let style = match self.style {
-            CharacterStyle::NextCharacter => Style::new()
-                .fg(tailwind::YELLOW.c300)
-                .bg(tailwind::GRAY.c100),
-            CharacterStyle::Correct => Style::new().fg(tailwind::GRAY.c800),
-            CharacterStyle::Incorrect => Style::new().fg(tailwind::RED.c500),
+            CharacterStyle::Caret(CaretStyle::Block) => {
+                Style::new().fg(theme.untyped_background).bg(theme.caret)
+            }
+            CharacterStyle::Stopped => Style::new()
+                .fg(theme.untyped_background)
+                .bg(theme.incorrect),
+            CharacterStyle::Caret(CaretStyle::Underline) => next_character
+                .fg(theme.caret)
+                .add_modifier(Modifier::UNDERLINED),
+            CharacterStyle::Caret(CaretStyle::None) | CharacterStyle::NextCharacter => {
+                next_character
+            }
+            CharacterStyle::Correct => Style::new().fg(theme.correct),
+            CharacterStyle::Incorrect => Style::new().fg(theme.incorrect),
+            CharacterStyle::Extra => Style::new().fg(theme.extra),
+            CharacterStyle::Missed => Style::new()
+                .fg(theme.missed)
+                .add_modifier(Modifier::UNDERLINED),
+            CharacterStyle::Ghost => Style::new().fg(theme.ghost).bg(theme.ghost_background),
         };
 
         Paragraph::new(self.char.to_string())
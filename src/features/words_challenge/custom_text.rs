@@ -0,0 +1,74 @@
+// Text given on the command line to practise on, split into the chunks that are typed one
+// challenge at a time
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomText {
+    chunks: Vec<String>,
+    // the chunk the next challenge types
+    next_chunk: usize,
+}
+
+impl CustomText {
+    // Collapses every run of whitespace, including newlines and tabs, into a single space so the
+    // text can be typed on one line. Without a chunk size the whole text is one challenge. Gives
+    // None when there is nothing to type
+    pub fn new(raw_text: &str, chunk_size: Option<usize>) -> Option<Self> {
+        let words: Vec<&str> = raw_text
+            .trim_start_matches('\u{feff}')
+            .split_whitespace()
+            .collect();
+        if words.is_empty() {
+            return None;
+        }
+
+        let chunk_size = chunk_size.unwrap_or(words.len()).max(1);
+        let chunks = words.chunks(chunk_size).map(|chunk| chunk.join(" ")).collect();
+
+        Some(Self {
+            chunks,
+            next_chunk: 0,
+        })
+    }
+
+    // the text of the next chunk, going back to the first after the last one
+    pub fn next_chunk(self) -> (Self, String) {
+        let chunk = self.chunks[self.next_chunk].clone();
+        let next_chunk = (self.next_chunk + 1) % self.chunks.len();
+
+        (Self { next_chunk, ..self }, chunk)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn whitespace_is_collapsed_to_single_spaces() {
+        let custom_text = CustomText::new("  first line\n\nsecond\tline \r\n", None).unwrap();
+
+        let (_, chunk) = custom_text.next_chunk();
+
+        assert_eq!(chunk, "first line second line");
+    }
+
+    #[test]
+    fn empty_text_has_nothing_to_type() {
+        assert_eq!(CustomText::new(" \n\t ", None), None);
+        assert_eq!(CustomText::new("", Some(10)), None);
+    }
+
+    #[test]
+    fn chunks_have_the_given_number_of_words_and_wrap_around() {
+        let custom_text = CustomText::new("one two three four five", Some(2)).unwrap();
+
+        let (custom_text, first) = custom_text.next_chunk();
+        let (custom_text, second) = custom_text.next_chunk();
+        let (custom_text, third) = custom_text.next_chunk();
+        let (_, fourth) = custom_text.next_chunk();
+
+        assert_eq!(first, "one two");
+        assert_eq!(second, "three four");
+        assert_eq!(third, "five");
+        assert_eq!(fourth, "one two");
+    }
+}
@@ -0,0 +1,86 @@
+use std::fmt;
+
+use serde::{de, Deserialize, Deserializer, Serialize};
+
+const DEFAULT_MIN_WPM_SECONDS: u64 = 5;
+
+// Ways a challenge can be failed before it's finished, none of them are on unless they're set
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FailConditions {
+    // the first mistake fails the challenge
+    pub sudden_death: bool,
+    // a percentage the accuracy can't drop below
+    #[serde(deserialize_with = "deserialize_min_accuracy")]
+    pub min_accuracy: Option<f32>,
+    // a wpm that the last few seconds of typing can't be slower than
+    #[serde(deserialize_with = "deserialize_min_wpm")]
+    pub min_wpm: Option<f32>,
+    #[serde(deserialize_with = "deserialize_min_wpm_seconds")]
+    pub min_wpm_seconds: u64,
+}
+
+impl Default for FailConditions {
+    fn default() -> Self {
+        Self {
+            sudden_death: false,
+            min_accuracy: None,
+            min_wpm: None,
+            min_wpm_seconds: DEFAULT_MIN_WPM_SECONDS,
+        }
+    }
+}
+
+// anything over 100% would fail every challenge on its first keystroke
+fn deserialize_min_accuracy<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<f32>, D::Error> {
+    let min_accuracy = f32::deserialize(deserializer)?;
+    if !(0.0..=100.0).contains(&min_accuracy) {
+        return Err(de::Error::custom("min_accuracy should be between 0 and 100"));
+    }
+
+    Ok(Some(min_accuracy))
+}
+
+fn deserialize_min_wpm<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<f32>, D::Error> {
+    let min_wpm = f32::deserialize(deserializer)?;
+    if min_wpm <= 0.0 {
+        return Err(de::Error::custom("min_wpm should be more than 0"));
+    }
+
+    Ok(Some(min_wpm))
+}
+
+// the wpm is checked over this many seconds, with none there'd be no typing to check
+fn deserialize_min_wpm_seconds<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    let seconds = u64::deserialize(deserializer)?;
+    if seconds == 0 {
+        return Err(de::Error::custom("min_wpm_seconds should be at least 1"));
+    }
+
+    Ok(seconds)
+}
+
+// Which of the fail conditions a failed challenge met, with the limit it was failed on
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailReason {
+    Mistake,
+    AccuracyBelow(f32),
+    WpmBelow { wpm: f32, seconds: u64 },
+}
+
+impl fmt::Display for FailReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FailReason::Mistake => write!(f, "made a mistake"),
+            FailReason::AccuracyBelow(min_accuracy) => {
+                write!(f, "accuracy dropped below {}%", min_accuracy)
+            }
+            FailReason::WpmBelow { wpm, seconds } => {
+                write!(f, "wpm stayed below {} for {}s", wpm, seconds)
+            }
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use std::{fmt, time::Duration};
+
+use super::{challenge_result::ChallengeResult, words_challenge_model::WordsChallengeModel};
+
+// Where a previous run's caret was at each point in time, so it can be raced against
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ghost {
+    // the caret position after each keystroke and when it was made, in the order they were made
+    timeline: Vec<(Duration, usize)>,
+}
+
+impl Ghost {
+    // Plays the run's keystrokes back through a challenge with the same text to find where each
+    // one left the caret, the same way a replay does, as extra letters, skipped words and wrong
+    // keys when stopping on errors all move it differently. Runs recorded before keystrokes were
+    // logged have nothing to follow
+    pub fn from_result(result: &ChallengeResult) -> Option<Self> {
+        if result.keystrokes.is_empty() {
+            return None;
+        }
+
+        let mut challenge = WordsChallengeModel::same_text_as(result);
+        let mut timeline = Vec::with_capacity(result.keystrokes.len());
+        for keystroke in &result.keystrokes {
+            challenge = challenge.replay_keystroke(keystroke);
+            timeline.push((keystroke.time, challenge.current_pos));
+        }
+
+        Some(Self { timeline })
+    }
+
+    // the ghost's caret position the given time after the challenge started
+    pub fn position_at(&self, elapsed: Duration) -> usize {
+        let keystrokes_made = self.timeline.partition_point(|(time, _)| *time <= elapsed);
+
+        match keystrokes_made {
+            0 => 0,
+            made => self.timeline[made - 1].1,
+        }
+    }
+}
+
+// Where a ghost to race against comes from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GhostSource {
+    #[default]
+    Off,
+    PersonalBest,
+    LastRun,
+}
+
+impl GhostSource {
+    pub fn next(&self) -> Self {
+        match self {
+            GhostSource::Off => GhostSource::PersonalBest,
+            GhostSource::PersonalBest => GhostSource::LastRun,
+            GhostSource::LastRun => GhostSource::Off,
+        }
+    }
+}
+
+impl fmt::Display for GhostSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GhostSource::Off => write!(f, "off"),
+            GhostSource::PersonalBest => write!(f, "personal best"),
+            GhostSource::LastRun => write!(f, "last run"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Local;
+
+    use crate::features::words_challenge::{
+        challenge_mode::ChallengeMode,
+        challenge_settings::ChallengeSettings,
+        keystroke::{Keystroke, KeystrokeKey},
+        stop_on_error::StopOnError,
+        word_generator::WordBand,
+    };
+
+    use super::*;
+
+    // a run typing the custom text with the given keys, one every 200ms
+    fn result(text: &str, stop_on_error: StopOnError, keys: &[KeystrokeKey]) -> ChallengeResult {
+        let keystrokes = keys
+            .iter()
+            .enumerate()
+            .map(|(index, key)| Keystroke {
+                key: *key,
+                expected: None,
+                position: 0,
+                time: Duration::from_millis(index as u64 * 200),
+            })
+            .collect();
+
+        ChallengeResult {
+            settings: ChallengeSettings::new(
+                ChallengeMode::Custom(text.split_whitespace().count()),
+                WordBand::Top200,
+            ),
+            seed: 0,
+            wpm: 60.0,
+            accuracy: 100.0,
+            duration: Duration::from_secs(1),
+            correct_characters: 0,
+            incorrect_characters: 0,
+            completed_at: Local::now(),
+            wpm_samples: Vec::new(),
+            keystrokes,
+            quote_id: None,
+            custom_text: Some(text.to_string()),
+            stop_on_error,
+            failure: None,
+            mistyped_words: Vec::new(),
+        }
+    }
+
+    fn typed(chars: &str) -> Vec<KeystrokeKey> {
+        chars
+            .chars()
+            .map(|char| match char {
+                '\x08' => KeystrokeKey::Backspace,
+                char => KeystrokeKey::Character(char),
+            })
+            .collect()
+    }
+
+    fn ghost() -> Ghost {
+        Ghost::from_result(&result("dog", StopOnError::Off, &typed("dx\x08o"))).unwrap()
+    }
+
+    #[test]
+    fn ghost_follows_keystrokes_over_time() {
+        let ghost = ghost();
+
+        assert_eq!(ghost.position_at(Duration::from_millis(0)), 1);
+        assert_eq!(ghost.position_at(Duration::from_millis(250)), 2);
+        assert_eq!(ghost.position_at(Duration::from_millis(450)), 1);
+        assert_eq!(ghost.position_at(Duration::from_millis(600)), 2);
+    }
+
+    #[test]
+    fn ghost_stays_at_end_after_last_keystroke() {
+        assert_eq!(ghost().position_at(Duration::from_secs(60)), 2);
+    }
+
+    #[test]
+    fn extra_letters_leave_the_ghost_at_the_end_of_the_word() {
+        let ghost = Ghost::from_result(&result("ab cd", StopOnError::Off, &typed("abx"))).unwrap();
+
+        assert_eq!(ghost.position_at(Duration::from_secs(60)), 2);
+    }
+
+    #[test]
+    fn wrong_key_when_stopping_on_errors_leaves_the_ghost_where_it_was() {
+        let ghost =
+            Ghost::from_result(&result("ab cd", StopOnError::Letter, &typed("ax"))).unwrap();
+
+        assert_eq!(ghost.position_at(Duration::from_secs(60)), 1);
+    }
+
+    #[test]
+    fn skipping_a_word_moves_the_ghost_to_the_next_one() {
+        let ghost = Ghost::from_result(&result("ab cd", StopOnError::Off, &typed("a "))).unwrap();
+
+        assert_eq!(ghost.position_at(Duration::from_secs(60)), 3);
+    }
+
+    #[test]
+    fn no_ghost_without_keystrokes() {
+        assert_eq!(Ghost::from_result(&result("dog", StopOnError::Off, &[])), None);
+    }
+}
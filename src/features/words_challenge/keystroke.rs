@@ -0,0 +1,101 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+// A single input made during a challenge, kept so that the typing can be looked at again later
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Keystroke {
+    pub key: KeystrokeKey,
+    // the character at the caret when the key was pressed, nothing if the caret was past the end
+    pub expected: Option<char>,
+    // where the caret was before the key was pressed
+    pub position: usize,
+    // how long after the first keystroke this one was made
+    #[serde(rename = "time_ms", with = "duration_millis")]
+    pub time: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeystrokeKey {
+    Character(char),
+    Backspace,
+}
+
+// A press of a key that should have typed a character, from a challenge's keystrokes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimedPress {
+    pub expected: char,
+    pub correct: bool,
+    // how long a correct press took after the key pressed before it
+    pub latency: Option<Duration>,
+}
+
+// Every key press that should have typed a character, the same way for the key stats and
+// adaptive practice. Backspaces and anything typed past the end of the text are left out, though
+// they're still what the next press is timed from. The first keystroke of a challenge has nothing
+// before it to time it from
+pub fn timed_presses(keystrokes: &[Keystroke]) -> impl Iterator<Item = TimedPress> + '_ {
+    let previous_times = [None]
+        .into_iter()
+        .chain(keystrokes.iter().map(|keystroke| Some(keystroke.time)));
+
+    keystrokes
+        .iter()
+        .zip(previous_times)
+        .filter_map(|(keystroke, previous_time)| {
+            let (KeystrokeKey::Character(typed), Some(expected)) =
+                (keystroke.key, keystroke.expected)
+            else {
+                return None;
+            };
+            let correct = typed == expected;
+            let latency = previous_time
+                .filter(|_| correct)
+                .map(|previous_time| keystroke.time.saturating_sub(previous_time));
+
+            Some(TimedPress {
+                expected,
+                correct,
+                latency,
+            })
+        })
+}
+
+// keystroke logs are stored with every result, so times are kept as whole milliseconds rather
+// than serde's default seconds and nanoseconds pair to keep them small
+mod duration_millis {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(duration.as_millis() as u64)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_millis)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keystroke_time_is_stored_as_milliseconds() {
+        let keystroke = Keystroke {
+            key: KeystrokeKey::Character('b'),
+            expected: Some('a'),
+            position: 3,
+            time: Duration::from_millis(1_250),
+        };
+
+        let json = serde_json::to_string(&keystroke).unwrap();
+        assert_eq!(
+            json,
+            r#"{"key":{"character":"b"},"expected":"a","position":3,"time_ms":1250}"#
+        );
+        assert_eq!(serde_json::from_str::<Keystroke>(&json).unwrap(), keystroke);
+    }
+}
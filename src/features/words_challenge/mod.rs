@@ -1,5 +1,20 @@
+pub(crate) mod backspace_policy;
+pub(crate) mod caret_style;
+pub(crate) mod challenge_mode;
+pub(crate) mod challenge_result;
+pub(crate) mod challenge_settings;
+pub(crate) mod custom_text;
+pub(crate) mod fail_conditions;
+pub(crate) mod ghost;
+pub(crate) mod keystroke;
+pub(crate) mod quote;
+pub(crate) mod stop_on_error;
+mod text_wrap;
 pub(crate) mod words_challenge_model;
 pub(crate) mod words_challenge_view;
 pub(crate) mod words_challenge_update;
+pub(crate) mod words_challenge_widget;
+pub(crate) mod word_generator;
+mod challenge_hud_widget;
 mod character_widget;
-mod words_challenge_widget;
+mod on_screen_keyboard_widget;
@@ -0,0 +1,69 @@
+use ratatui::prelude::*;
+
+use crate::{
+    config::theme::Theme,
+    features::keyboard::{
+        finger::Finger,
+        keyboard_layout::{KeyboardKey, KeyboardLayout},
+        keyboard_widget::KeyboardWidget,
+    },
+};
+
+use super::words_challenge_model::WordsChallengeModel;
+
+// A keyboard under the text for learning to touch type. Every key is drawn in the colour of the
+// finger that presses it, the next key to press is filled in with that colour along with the
+// shift key to hold for it, and a key pressed by mistake flashes
+pub struct OnScreenKeyboardWidget<'a> {
+    pub challenge_model: &'a WordsChallengeModel,
+    pub layout: &'a KeyboardLayout,
+    pub theme: &'a Theme,
+}
+
+impl OnScreenKeyboardWidget<'_> {
+    fn finger_colour(&self, finger: Finger) -> Color {
+        match finger {
+            Finger::Pinky => self.theme.finger_pinky,
+            Finger::Ring => self.theme.finger_ring,
+            Finger::Middle => self.theme.finger_middle,
+            Finger::Index => self.theme.finger_index,
+            Finger::Thumb => self.theme.finger_thumb,
+        }
+    }
+}
+
+impl Widget for OnScreenKeyboardWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let next_character = self.challenge_model.next_character();
+        let next_key = next_character.and_then(|char| self.layout.key_for(char));
+        let shift_key = next_character.and_then(|char| self.layout.shift_for(char));
+
+        let wrong_key = self
+            .challenge_model
+            .wrong_key()
+            .and_then(|char| self.layout.key_for(char));
+
+        let key_style = |key: KeyboardKey| {
+            let finger_colour = self.finger_colour(key.finger());
+            if Some(key) == wrong_key {
+                Style::new()
+                    .fg(self.theme.key_text)
+                    .bg(self.theme.incorrect)
+            } else if Some(key) == next_key || Some(key) == shift_key {
+                Style::new().fg(self.theme.key_text).bg(finger_colour)
+            } else {
+                Style::new().fg(finger_colour)
+            }
+        };
+
+        KeyboardWidget {
+            layout: self.layout,
+            show_shift: true,
+            key_style,
+        }
+        .render(area, buf);
+    }
+}
@@ -0,0 +1,169 @@
+use std::{fmt, str::FromStr, sync::OnceLock};
+
+use rand::{seq::SliceRandom, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::Deserialize;
+
+// Passages to type in quote mode with where they came from, ids must never be reused as they are
+// saved with results
+const ENGLISH_QUOTES: &str = include_str!("quotes/english.json");
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Quote {
+    pub id: u32,
+    pub text: String,
+    pub source: String,
+}
+
+// How long a quote is, in the same buckets as monkeytype
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuoteLength {
+    // up to 100 characters
+    Short,
+    // up to 300 characters
+    Medium,
+    // up to 600 characters
+    Long,
+    Thicc,
+}
+
+impl QuoteLength {
+    pub const ALL: [QuoteLength; 4] = [
+        QuoteLength::Short,
+        QuoteLength::Medium,
+        QuoteLength::Long,
+        QuoteLength::Thicc,
+    ];
+
+    fn of_text(text: &str) -> Self {
+        match text.chars().count() {
+            0..=100 => QuoteLength::Short,
+            101..=300 => QuoteLength::Medium,
+            301..=600 => QuoteLength::Long,
+            _ => QuoteLength::Thicc,
+        }
+    }
+}
+
+impl fmt::Display for QuoteLength {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuoteLength::Short => write!(f, "short"),
+            QuoteLength::Medium => write!(f, "medium"),
+            QuoteLength::Long => write!(f, "long"),
+            QuoteLength::Thicc => write!(f, "thicc"),
+        }
+    }
+}
+
+impl FromStr for QuoteLength {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|length| length.to_string() == name)
+            .ok_or_else(|| format!("unknown quote length '{}'", name))
+    }
+}
+
+impl Quote {
+    pub fn all() -> &'static [Quote] {
+        static QUOTES: OnceLock<Vec<Quote>> = OnceLock::new();
+        QUOTES.get_or_init(|| {
+            serde_json::from_str(ENGLISH_QUOTES).expect("bundled quotes should be valid json")
+        })
+    }
+
+    pub fn by_id(id: u32) -> Option<&'static Quote> {
+        Self::all().iter().find(|quote| quote.id == id)
+    }
+
+    // picks a quote of the given length, the same seed always picks the same quote
+    pub fn random(length: QuoteLength, seed: u64) -> &'static Quote {
+        let quotes: Vec<&Quote> = Self::all()
+            .iter()
+            .filter(|quote| quote.length() == length)
+            .collect();
+
+        quotes
+            .choose(&mut ChaCha8Rng::seed_from_u64(seed))
+            .expect("every quote length should have quotes")
+    }
+
+    // every quote whose text or source contains the query, ignoring case
+    pub fn search(query: &str) -> Vec<&'static Quote> {
+        let query = query.to_lowercase();
+
+        Self::all()
+            .iter()
+            .filter(|quote| {
+                quote.text.to_lowercase().contains(&query)
+                    || quote.source.to_lowercase().contains(&query)
+            })
+            .collect()
+    }
+
+    pub fn length(&self) -> QuoteLength {
+        QuoteLength::of_text(&self.text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn every_length_has_quotes() {
+        for length in QuoteLength::ALL {
+            assert!(
+                Quote::all().iter().any(|quote| quote.length() == length),
+                "no {} quotes",
+                length
+            );
+        }
+    }
+
+    #[test]
+    fn quote_ids_are_unique() {
+        let ids: HashSet<u32> = Quote::all().iter().map(|quote| quote.id).collect();
+        assert_eq!(ids.len(), Quote::all().len());
+    }
+
+    #[test]
+    fn quotes_only_use_single_spaces() {
+        assert!(Quote::all()
+            .iter()
+            .all(|quote| !quote.text.contains("  ") && quote.text.trim() == quote.text));
+    }
+
+    #[test]
+    fn same_seed_picks_same_quote() {
+        let first = Quote::random(QuoteLength::Medium, 12);
+        let second = Quote::random(QuoteLength::Medium, 12);
+
+        assert_eq!(first, second);
+        assert_eq!(first.length(), QuoteLength::Medium);
+    }
+
+    #[test]
+    fn search_matches_text_and_source_ignoring_case() {
+        let by_source = Quote::search("gettysburg");
+        assert_eq!(by_source.len(), 1);
+        assert!(by_source[0].text.starts_with("Four score"));
+
+        let by_text = Quote::search("CALL ME ISHMAEL");
+        assert_eq!(by_text.len(), 1);
+        assert_eq!(by_text[0].source, "Herman Melville, Moby-Dick");
+    }
+
+    #[test]
+    fn lengths_are_bucketed_by_characters() {
+        assert_eq!(QuoteLength::of_text(&"a".repeat(100)), QuoteLength::Short);
+        assert_eq!(QuoteLength::of_text(&"a".repeat(101)), QuoteLength::Medium);
+        assert_eq!(QuoteLength::of_text(&"a".repeat(600)), QuoteLength::Long);
+        assert_eq!(QuoteLength::of_text(&"a".repeat(601)), QuoteLength::Thicc);
+    }
+}
@@ -0,0 +1,13 @@
+use serde::Deserialize;
+
+// Whether a mistake has to be put right before the caret moves on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StopOnError {
+    #[default]
+    Off,
+    // the caret stays on a letter until the right key is pressed
+    Letter,
+    // letters can be typed wrong but space won't move on to the next word until the word is right
+    Word,
+}
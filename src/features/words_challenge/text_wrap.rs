@@ -0,0 +1,108 @@
+use std::ops::Range;
+
+// Splits the text into lines no wider than the given width, breaking between words. Each line is
+// the range of character indices it covers and keeps the space after its last word, so the caret
+// still has somewhere to sit when it's on that space. A word too long for a line of its own is
+// broken wherever it runs out of room
+pub fn wrap_text(text: &str, width: usize) -> Vec<Range<usize>> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    let mut line_start = 0;
+    let mut word_start = 0;
+
+    for (index, char) in text.chars().chain([' ']).enumerate() {
+        let line_is_full = if char == ' ' {
+            // the space after a word has to fit on the same line as the word
+            index + 1 - line_start > width
+        } else {
+            index - line_start == width
+        };
+
+        if line_is_full {
+            // the word moves down to the next line, unless it's already at the start of one
+            // in which case it's too long to fit anywhere and gets broken where it is
+            let break_at = if word_start > line_start {
+                word_start
+            } else {
+                index
+            };
+            lines.push(line_start..break_at);
+            line_start = break_at;
+
+            // the word fits on the new line but the space after it doesn't
+            if char == ' ' && index + 1 - line_start > width {
+                lines.push(line_start..index);
+                line_start = index;
+            }
+        }
+
+        if char == ' ' {
+            word_start = index + 1;
+        }
+    }
+
+    let text_length = text.chars().count();
+    if line_start < text_length || lines.is_empty() {
+        lines.push(line_start..text_length);
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wrapped_lines(text: &str, width: usize) -> Vec<String> {
+        let chars: Vec<char> = text.chars().collect();
+        wrap_text(text, width)
+            .into_iter()
+            .map(|line| chars[line].iter().collect())
+            .collect()
+    }
+
+    #[test]
+    fn short_text_fits_on_one_line() {
+        assert_eq!(wrapped_lines("the cat sat", 80), vec!["the cat sat"]);
+    }
+
+    #[test]
+    fn lines_break_between_words() {
+        assert_eq!(
+            wrapped_lines("the cat sat on the mat", 10),
+            vec!["the cat ", "sat on ", "the mat"]
+        );
+    }
+
+    #[test]
+    fn space_after_a_word_that_fills_the_line_moves_it_down() {
+        assert_eq!(wrapped_lines("abcd efgh", 4), vec!["abcd", " ", "efgh"]);
+        assert_eq!(wrapped_lines("ab cd efgh", 6), vec!["ab cd ", "efgh"]);
+    }
+
+    #[test]
+    fn words_longer_than_a_line_are_broken() {
+        assert_eq!(
+            wrapped_lines("a abcdefgh b", 4),
+            vec!["a ", "abcd", "efgh", " b"]
+        );
+    }
+
+    #[test]
+    fn empty_text_has_one_empty_line() {
+        assert_eq!(wrap_text("", 80), vec![0..0]);
+    }
+
+    #[test]
+    fn lines_cover_every_character_in_order() {
+        let text = "the quick brown fox jumps over the lazy dog and keeps on running";
+        let lines = wrap_text(text, 12);
+
+        assert_eq!(lines.first().unwrap().start, 0);
+        assert_eq!(lines.last().unwrap().end, text.chars().count());
+        for pair in lines.windows(2) {
+            assert_eq!(pair[0].end, pair[1].start);
+        }
+        assert!(lines.iter().all(|line| line.len() <= 12));
+    }
+}
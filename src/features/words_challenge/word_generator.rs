@@ -0,0 +1,167 @@
+use std::{fmt, str::FromStr, sync::OnceLock};
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+// English words ordered from most to least frequent, one per line
+const ENGLISH_WORDS: &str = include_str!("word_lists/english.txt");
+
+fn english_words() -> &'static [&'static str] {
+    static WORDS: OnceLock<Vec<&'static str>> = OnceLock::new();
+    WORDS.get_or_init(|| ENGLISH_WORDS.lines().collect())
+}
+
+// How far down the frequency ranking words can be drawn from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WordBand {
+    Top200,
+    Top1k,
+    Top10k,
+}
+
+impl WordBand {
+    pub const ALL: [WordBand; 3] = [WordBand::Top200, WordBand::Top1k, WordBand::Top10k];
+
+    // the name given on the command line, these follow monkeytype's language names
+    pub fn language_name(&self) -> &'static str {
+        match self {
+            WordBand::Top200 => "english",
+            WordBand::Top1k => "english_1k",
+            WordBand::Top10k => "english_10k",
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        match self {
+            WordBand::Top200 => 200,
+            WordBand::Top1k => 1_000,
+            WordBand::Top10k => 10_000,
+        }
+    }
+
+    pub fn words(&self) -> &'static [&'static str] {
+        let words = english_words();
+        &words[..self.size().min(words.len())]
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            WordBand::Top200 => WordBand::Top1k,
+            WordBand::Top1k => WordBand::Top10k,
+            WordBand::Top10k => WordBand::Top200,
+        }
+    }
+}
+
+impl fmt::Display for WordBand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WordBand::Top200 => write!(f, "english 200"),
+            WordBand::Top1k => write!(f, "english 1k"),
+            WordBand::Top10k => write!(f, "english 10k"),
+        }
+    }
+}
+
+impl FromStr for WordBand {
+    type Err = String;
+
+    fn from_str(language_name: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|band| band.language_name() == language_name)
+            .ok_or_else(|| format!("unknown language '{}'", language_name))
+    }
+}
+
+// Draws random words from a frequency band. The rng is ChaCha so that a given seed produces
+// the same words on every platform and version, which lets a test be regenerated from its seed
+#[derive(Debug, Clone)]
+pub struct WordGenerator {
+    band: WordBand,
+    seed: u64,
+    rng: ChaCha8Rng,
+    previous_word: Option<&'static str>,
+}
+
+impl WordGenerator {
+    pub fn new(band: WordBand, seed: u64) -> Self {
+        Self {
+            band,
+            seed,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+            previous_word: None,
+        }
+    }
+
+    pub fn band(&self) -> WordBand {
+        self.band
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    // picks the next word, never giving the same word twice in a row
+    pub fn next_word(&mut self) -> &'static str {
+        let words = self.band.words();
+
+        loop {
+            let word = words[self.rng.gen_range(0..words.len())];
+            if self.previous_word != Some(word) {
+                self.previous_word = Some(word);
+                return word;
+            }
+        }
+    }
+
+    pub fn words(&mut self, count: usize) -> Vec<&'static str> {
+        (0..count).map(|_| self.next_word()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bands_have_expected_sizes() {
+        assert_eq!(WordBand::Top200.words().len(), 200);
+        assert_eq!(WordBand::Top1k.words().len(), 1_000);
+        assert_eq!(WordBand::Top10k.words().len(), 10_000);
+    }
+
+    #[test]
+    fn bands_are_ordered_by_frequency() {
+        assert_eq!(WordBand::Top200.words()[0], "the");
+        assert_eq!(WordBand::Top200.words(), &WordBand::Top10k.words()[..200]);
+    }
+
+    #[test]
+    fn same_seed_generates_same_words() {
+        let first = WordGenerator::new(WordBand::Top1k, 42).words(20);
+        let second = WordGenerator::new(WordBand::Top1k, 42).words(20);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn words_are_drawn_from_band() {
+        let band_words = WordBand::Top200.words();
+        let words = WordGenerator::new(WordBand::Top200, 7).words(500);
+        assert!(words.iter().all(|word| band_words.contains(word)));
+    }
+
+    #[test]
+    fn same_word_never_appears_twice_in_a_row() {
+        let words = WordGenerator::new(WordBand::Top200, 3).words(1_000);
+        assert!(words.windows(2).all(|pair| pair[0] != pair[1]));
+    }
+
+    #[test]
+    fn language_names_parse_back_to_their_band() {
+        for band in WordBand::ALL {
+            assert_eq!(band.language_name().parse(), Ok(band));
+        }
+        assert!("klingon".parse::<WordBand>().is_err());
+    }
+}
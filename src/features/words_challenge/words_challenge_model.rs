@@ -1,68 +1,551 @@
-use std::{collections::HashSet, time::Instant};
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    time::{Duration, Instant},
+};
+use chrono::Local;
 use color_eyre::Result;
 
 use crossterm::event::{KeyCode, KeyEvent};
 
-use crate::tui::message::Message;
+use crate::{features::adaptive::adaptive_progress::AdaptiveLetters, tui::message::Message};
+
+use super::{
+    backspace_policy::BackspacePolicy,
+    challenge_mode::ChallengeMode,
+    challenge_result::{ChallengeResult, WpmSample},
+    challenge_settings::ChallengeSettings,
+    fail_conditions::{FailConditions, FailReason},
+    ghost::Ghost,
+    keystroke::{Keystroke, KeystrokeKey},
+    quote::Quote,
+    stop_on_error::StopOnError,
+    word_generator::{WordBand, WordGenerator},
+};
+
+// how many words a timed challenge starts with, and how many more are added each time it runs low
+const TIMED_WORDS_BATCH: usize = 40;
+// a timed challenge grows once the caret is within this many characters of the end of the text
+const TIMED_GROWTH_THRESHOLD: usize = 60;
+// letters typed past the end of a word stop being added after this many
+const MAX_EXTRA_LETTERS: usize = 20;
+// the accuracy swings too much to fail on until this many characters have been typed, unless the
+// challenge is over sooner
+const MIN_ACCURACY_GRACE_CHARACTERS: usize = 10;
+// how long the on-screen keyboard shows a key pressed by mistake
+const WRONG_KEY_FLASH: Duration = Duration::from_millis(300);
 
 #[derive(Debug)]
 pub struct WordsChallengeModel {
+    mode: ChallengeMode,
+    word_generator: WordGenerator,
+    // the quote being typed in quote mode
+    quote: Option<&'static Quote>,
+    // the letters an adaptive lesson is practising, nothing when replaying or racing one
+    adaptive_letters: Option<AdaptiveLetters>,
     pub(super) text: String,
     pub(super) text_length: usize,
-    text_word_count: usize,
+    pub(super) text_word_count: usize,
     // the cursor location in the test
     pub(super) current_pos: usize,
     finished: bool,
     pub(super) incorrect_indices: HashSet<usize>,
+    // letters typed past the end of a word, kept by the position of the space after the word (or
+    // the end of the text for the last word) which is where the caret waits while they're typed
+    pub(super) extra_letters: HashMap<usize, String>,
+    // letters jumped over by pressing space partway through a word
+    pub(super) missed_indices: HashSet<usize>,
+    // letters currently typed wrong, unlike the incorrect ones these are forgotten once the letter
+    // is deleted or typed again correctly
+    pub(super) uncorrected_indices: HashSet<usize>,
+    // where each word that had any mistake made in it starts, which unlike the indices above is
+    // never forgotten when the mistake is put right
+    mistyped_word_starts: BTreeSet<usize>,
     start_time: Option<Instant>,
     end_time: Option<Instant>,
+    // every character typed and every incorrect one, including those later deleted
+    keystroke_count: usize,
+    error_count: usize,
+    // one entry per wpm sample, the keystrokes and errors are those made since the last sample
     running_wpm: Vec<f32>,
+    running_keystrokes: Vec<usize>,
+    running_errors: Vec<usize>,
+    // every character and backspace input, in the order they were made
+    pub(super) keystrokes: Vec<Keystroke>,
+    // an earlier run being raced against
+    ghost: Option<Ghost>,
+    backspace_policy: BackspacePolicy,
+    stop_on_error: StopOnError,
+    fail_conditions: FailConditions,
+    // why the challenge ended early, if it was failed
+    failure: Option<FailReason>,
 }
 
 impl WordsChallengeModel {
-    pub fn generate(num_words: usize) -> Self {
-        let text = "bongle ".repeat(num_words);
+    pub fn new(mode: ChallengeMode, mut word_generator: WordGenerator) -> Self {
+        let (text, quote) = match mode {
+            ChallengeMode::Words(num_words) => (word_generator.words(num_words).join(" "), None),
+            ChallengeMode::Time(_) => (word_generator.words(TIMED_WORDS_BATCH).join(" "), None),
+            // the seed picks the quote so that retrying with the same seed gives the same quote
+            ChallengeMode::Quote(length) => {
+                let quote = Quote::random(length, word_generator.seed());
+                (quote.text.clone(), Some(quote))
+            }
+            ChallengeMode::Custom(_) => {
+                panic!("custom text can't be generated, it should be given to from_custom_text")
+            }
+            ChallengeMode::Adaptive(_) => {
+                panic!("adaptive lessons are made from progress, give them to from_adaptive_text")
+            }
+        };
+
+        Self::with_text(mode, word_generator, text, quote)
+    }
+
+    // a challenge for typing one chosen quote
+    pub fn from_quote(quote: &'static Quote) -> Self {
+        // the word generator is never used for a quote, it's only kept to give the settings
+        Self::with_text(
+            ChallengeMode::Quote(quote.length()),
+            WordGenerator::new(WordBand::Top200, 0),
+            quote.text.clone(),
+            Some(quote),
+        )
+    }
+
+    // a challenge for typing text from outside the app, which should already be normalised
+    pub fn from_custom_text(text: String) -> Self {
+        // like a quote, the word generator is never used for custom text
+        Self::with_text(
+            ChallengeMode::Custom(text.split_whitespace().count()),
+            WordGenerator::new(WordBand::Top200, 0),
+            text,
+            None,
+        )
+    }
+
+    // a challenge for typing an adaptive lesson made up from the progress so far
+    pub fn from_adaptive_text(text: String) -> Self {
+        // the words were made up before the challenge, the word generator is never used either
+        Self::with_text(
+            ChallengeMode::Adaptive(text.split_whitespace().count()),
+            WordGenerator::new(WordBand::Top200, 0),
+            text,
+            None,
+        )
+    }
+
+    // a challenge with the same text as a finished one, for replaying or racing against it, which
+    // stops on errors the same way it did
+    pub fn same_text_as(result: &ChallengeResult) -> Self {
+        let challenge = match (&result.custom_text, result.quote_id.and_then(Quote::by_id)) {
+            (Some(text), _) if matches!(result.settings.mode, ChallengeMode::Adaptive(_)) => {
+                Self::from_adaptive_text(text.clone())
+            }
+            (Some(text), _) => Self::from_custom_text(text.clone()),
+            (None, Some(quote)) => Self::from_quote(quote),
+            (None, None) => Self::new(
+                result.settings.mode,
+                WordGenerator::new(result.settings.word_band, result.seed),
+            ),
+        };
+
+        challenge.with_stop_on_error(result.stop_on_error)
+    }
+
+    fn with_text(
+        mode: ChallengeMode,
+        word_generator: WordGenerator,
+        text: String,
+        quote: Option<&'static Quote>,
+    ) -> Self {
         let text_length = text.chars().count();
         let text_word_count = text.split_whitespace().count();
 
         Self {
+            mode,
+            word_generator,
+            quote,
+            adaptive_letters: None,
             text,
             text_length,
             text_word_count,
             current_pos: 0,
             finished: false,
             incorrect_indices: HashSet::new(),
+            extra_letters: HashMap::new(),
+            missed_indices: HashSet::new(),
+            uncorrected_indices: HashSet::new(),
+            mistyped_word_starts: BTreeSet::new(),
             start_time: None,
             end_time: None,
+            keystroke_count: 0,
+            error_count: 0,
             running_wpm: Vec::new(),
+            running_keystrokes: Vec::new(),
+            running_errors: Vec::new(),
+            keystrokes: Vec::new(),
+            ghost: None,
+            backspace_policy: BackspacePolicy::default(),
+            stop_on_error: StopOnError::default(),
+            fail_conditions: FailConditions::default(),
+            failure: None,
+        }
+    }
+
+    pub fn with_ghost(self, ghost: Option<Ghost>) -> Self {
+        Self { ghost, ..self }
+    }
+
+    pub fn with_backspace_policy(self, backspace_policy: BackspacePolicy) -> Self {
+        Self {
+            backspace_policy,
+            ..self
+        }
+    }
+
+    pub fn with_stop_on_error(self, stop_on_error: StopOnError) -> Self {
+        Self {
+            stop_on_error,
+            ..self
+        }
+    }
+
+    pub fn with_fail_conditions(self, fail_conditions: FailConditions) -> Self {
+        Self {
+            fail_conditions,
+            ..self
+        }
+    }
+
+    pub fn with_adaptive_letters(self, adaptive_letters: AdaptiveLetters) -> Self {
+        Self {
+            adaptive_letters: Some(adaptive_letters),
+            ..self
+        }
+    }
+
+    fn can_backspace(&self) -> bool {
+        match self.backspace_policy {
+            BackspacePolicy::Always => true,
+            BackspacePolicy::WithinWord => {
+                self.current_pos > 0 && self.text.chars().nth(self.current_pos - 1) != Some(' ')
+            }
+            BackspacePolicy::Never => false,
+        }
+    }
+
+    // where the ghost's caret is at this point in the challenge, it waits at the start until the
+    // first key is pressed
+    pub(super) fn ghost_position(&self) -> Option<usize> {
+        let ghost = self.ghost.as_ref()?;
+
+        match self.elapsed() {
+            Some(elapsed) => Some(ghost.position_at(elapsed)),
+            None => Some(0),
+        }
+    }
+
+    // how many characters the caret is ahead of the ghost, negative when behind
+    pub(super) fn ghost_lead(&self) -> Option<i64> {
+        self.ghost_position()
+            .map(|ghost_position| self.current_pos as i64 - ghost_position as i64)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    // the character the next key press should type, nothing once the challenge is over
+    pub(super) fn next_character(&self) -> Option<char> {
+        if self.finished {
+            return None;
+        }
+
+        self.text.chars().nth(self.current_pos)
+    }
+
+    // the key just pressed by mistake, for a moment after it was pressed
+    pub(super) fn wrong_key(&self) -> Option<char> {
+        let keystroke = self.keystrokes.last()?;
+        let KeystrokeKey::Character(typed) = keystroke.key else {
+            return None;
+        };
+
+        let since_pressed = self.elapsed()?.checked_sub(keystroke.time)?;
+        let is_shown = keystroke.expected != Some(typed) && since_pressed < WRONG_KEY_FLASH;
+        is_shown.then_some(typed)
+    }
+
+    pub(super) fn adaptive_letters(&self) -> Option<&AdaptiveLetters> {
+        self.adaptive_letters.as_ref()
+    }
+
+    pub fn settings(&self) -> ChallengeSettings {
+        ChallengeSettings::new(self.mode, self.word_generator.band())
+    }
+
+    // summarises a finished challenge, will panic for unfinished or unstarted challenge
+    pub fn result(&self) -> ChallengeResult {
+        let incorrect_characters = self.incorrect_character_count();
+        let correct_characters = self.typed_length() + self.extra_letter_count() - incorrect_characters;
+
+        ChallengeResult {
+            settings: self.settings(),
+            seed: self.word_generator.seed(),
+            wpm: self.wpm(),
+            accuracy: self.accuracy_percent(),
+            duration: self.elapsed().expect("finished challenge should have started"),
+            correct_characters,
+            incorrect_characters,
+            completed_at: Local::now(),
+            wpm_samples: self.wpm_samples(),
+            keystrokes: self.keystrokes.clone(),
+            quote_id: self.quote.map(|quote| quote.id),
+            custom_text: match self.mode {
+                ChallengeMode::Custom(_) | ChallengeMode::Adaptive(_) => Some(self.text.clone()),
+                _ => None,
+            },
+            stop_on_error: self.stop_on_error,
+            failure: self.failure,
+            mistyped_words: self.mistyped_words(),
         }
     }
 
+    // the same word mistyped in more than one place is only kept where it was first
+    fn mistyped_words(&self) -> Vec<String> {
+        let mut mistyped_words: Vec<String> = Vec::new();
+        for word_start in &self.mistyped_word_starts {
+            let word: String = self
+                .text
+                .chars()
+                .skip(*word_start)
+                .take_while(|char| *char != ' ')
+                .collect();
+            if !mistyped_words.contains(&word) {
+                mistyped_words.push(word);
+            }
+        }
+
+        mistyped_words
+    }
 
     pub fn handle_event(&self, key_event: KeyEvent) -> Result<Option<Message>> {
         Ok(Some(Message::ChallengeLetterInput(key_event.code)))
     }
 
     pub(super) fn handle_challenge_input(self, keycode: KeyCode) -> Self {
+        // input arriving after the time ran out is not part of the challenge
+        let now = Instant::now();
+        if self.is_out_of_time(now) {
+            return self.check_time_limit(now);
+        }
+
         let start_time = match self.start_time {
             Some(_) => self.start_time,
-            None => Some(Instant::now()),
+            None => Some(now),
+        };
+
+        let grown_self = self.grow_text_if_needed();
+
+        // a backspace that isn't allowed is left out of the keystrokes so replays stay the same
+        let key = match keycode {
+            KeyCode::Char(char) => KeystrokeKey::Character(char),
+            KeyCode::Backspace if grown_self.can_backspace() => KeystrokeKey::Backspace,
+            _ => return Self { start_time, ..grown_self },
         };
+        let since_start = now.duration_since(start_time.expect("start time was just set"));
+        let logged_self = grown_self.log_keystroke(key, since_start);
 
-        let new_self = match keycode {
-            KeyCode::Char(char) => self.handle_character(char),
-            KeyCode::Backspace => self.handle_backspace(),
-            _ => self,
+        let new_self = match key {
+            KeystrokeKey::Character(char) => logged_self.handle_character(char),
+            KeystrokeKey::Backspace => logged_self.handle_backspace(),
         };
 
         Self {
             start_time,
             ..new_self
         }
+        .check_fail_conditions(now)
+    }
+
+    // feeds in a recorded keystroke without touching the clock, for playing back a past challenge
+    pub fn replay_keystroke(self, keystroke: &Keystroke) -> Self {
+        if self.finished {
+            return self;
+        }
+
+        let grown_self = self.grow_text_if_needed();
+        match keystroke.key {
+            KeystrokeKey::Character(char) => grown_self.handle_character(char),
+            KeystrokeKey::Backspace => grown_self.handle_backspace(),
+        }
+    }
+
+    fn log_keystroke(mut self, key: KeystrokeKey, time: Duration) -> Self {
+        self.keystrokes.push(Keystroke {
+            key,
+            expected: self.text.chars().nth(self.current_pos),
+            position: self.current_pos,
+            time,
+        });
+        self
+    }
+
+    // called on every app tick to end timed challenges and sample the wpm once a second
+    pub fn tick(self, now: Instant) -> Self {
+        let checked_self = self.check_time_limit(now);
+
+        let samples_due = checked_self.elapsed_at(now).map(|elapsed| elapsed.as_secs() as usize);
+        let sampled_self = match samples_due {
+            Some(samples_due) if !checked_self.finished && checked_self.running_wpm.len() < samples_due => {
+                checked_self.poll_wpm()
+            }
+            _ => checked_self,
+        };
+
+        sampled_self.check_fail_conditions(now)
+    }
+
+    // ends the challenge as failed as soon as it meets one of the fail conditions, it's only
+    // checked while typing and when the challenge has just finished
+    fn check_fail_conditions(self, now: Instant) -> Self {
+        if self.start_time.is_none() || self.failure.is_some() {
+            return self;
+        }
+
+        let conditions = self.fail_conditions;
+        let accuracy_counts = self.finished || self.typed_length() >= MIN_ACCURACY_GRACE_CHARACTERS;
+
+        let failure = if conditions.sudden_death && self.error_count > 0 {
+            Some(FailReason::Mistake)
+        } else if let Some(min_accuracy) = conditions
+            .min_accuracy
+            .filter(|min_accuracy| accuracy_counts && self.accuracy_percent() < *min_accuracy)
+        {
+            Some(FailReason::AccuracyBelow(min_accuracy))
+        } else {
+            conditions
+                .min_wpm
+                .filter(|min_wpm| {
+                    self.rolling_wpm(conditions.min_wpm_seconds)
+                        .is_some_and(|wpm| wpm < *min_wpm)
+                })
+                .map(|min_wpm| FailReason::WpmBelow {
+                    wpm: min_wpm,
+                    seconds: conditions.min_wpm_seconds,
+                })
+        };
+
+        match failure {
+            Some(failure) => Self {
+                failure: Some(failure),
+                finished: true,
+                end_time: self.end_time.or(Some(now)),
+                ..self
+            },
+            None => self,
+        }
+    }
+
+    // the wpm over the last given number of seconds of samples, counting each correct key press
+    // as a fifth of a word, there isn't one until that many seconds have been sampled
+    fn rolling_wpm(&self, seconds: u64) -> Option<f32> {
+        let seconds = seconds as usize;
+        if seconds == 0 || self.running_keystrokes.len() < seconds {
+            return None;
+        }
+
+        let first_sample = self.running_keystrokes.len() - seconds;
+        let correct_keystrokes: usize = self.running_keystrokes[first_sample..]
+            .iter()
+            .zip(&self.running_errors[first_sample..])
+            .map(|(keystrokes, errors)| keystrokes.saturating_sub(*errors))
+            .sum();
+
+        Some(correct_keystrokes as f32 / 5.0 / (seconds as f32 / 60.0))
+    }
+
+    fn elapsed_at(&self, now: Instant) -> Option<Duration> {
+        let start_time = self.start_time?;
+        let end_time = self.end_time.unwrap_or(now);
+        Some(end_time.duration_since(start_time))
+    }
+
+    // how long the challenge has been running, stopping at the end time once finished
+    pub fn elapsed(&self) -> Option<Duration> {
+        self.elapsed_at(Instant::now())
+    }
+
+    // the time left in a timed challenge, the full limit before it starts
+    pub fn remaining_time(&self) -> Option<Duration> {
+        let time_limit = self.mode.time_limit()?;
+        let elapsed = self.elapsed().unwrap_or_default();
+        Some(time_limit.saturating_sub(elapsed))
+    }
+
+    pub fn mode(&self) -> ChallengeMode {
+        self.mode
+    }
+
+    // the most recent wpm sample, or nothing if none have been taken yet
+    pub fn latest_wpm(&self) -> Option<f32> {
+        self.running_wpm.last().copied()
+    }
+
+    fn is_out_of_time(&self, now: Instant) -> bool {
+        match (self.mode.time_limit(), self.start_time) {
+            (Some(time_limit), Some(start_time)) => {
+                !self.finished && now.duration_since(start_time) >= time_limit
+            }
+            _ => false,
+        }
+    }
+
+    // finishes a timed challenge whose time has run out, the end time is clamped to the limit so
+    // late checks don't count as extra typing time
+    pub fn check_time_limit(self, now: Instant) -> Self {
+        if !self.is_out_of_time(now) {
+            return self;
+        }
+
+        let end_time = self.start_time.zip(self.mode.time_limit()).map(|(start, limit)| start + limit);
+
+        Self {
+            finished: true,
+            end_time,
+            ..self
+        }
+    }
+
+    // timed challenges have no fixed text so more words are added as the caret nears the end
+    fn grow_text_if_needed(mut self) -> Self {
+        if self.mode.time_limit().is_none()
+            || self.text_length - self.current_pos > TIMED_GROWTH_THRESHOLD
+        {
+            return self;
+        }
+
+        for word in self.word_generator.words(TIMED_WORDS_BATCH) {
+            self.text.push(' ');
+            self.text.push_str(word);
+        }
+        self.text_length = self.text.chars().count();
+        self.text_word_count += TIMED_WORDS_BATCH;
+
+        self
+    }
+
+    // the number of words typed before the caret, a word counts once the space after it is typed
+    pub fn typed_word_count(&self) -> usize {
+        self.text
+            .chars()
+            .take(self.current_pos)
+            .filter(|c| c == &' ')
+            .count()
     }
 
     // calculates the wpm of a finished test, will panic for unfinished or unstarted test
-    pub fn wpm(self) -> f32 {
+    pub fn wpm(&self) -> f32 {
         if self.start_time.is_none() {
             panic!("cannot calculate wpm for unstarted challenge");
         }
@@ -77,14 +560,30 @@ impl WordsChallengeModel {
             .as_secs_f32()
             / 60.0;
 
+        // a challenge can be failed on its very first key press
+        if challenge_time_mins <= 0.0 && self.failure.is_some() {
+            return 0.0;
+        }
         if challenge_time_mins <= 0.0 {
             panic!("shouldn't have test duration with no or negative time");
         }
 
-        let wpm = self.text_word_count as f32 / challenge_time_mins;
-        let rounded_wpm = (wpm * 10.0).round() / 10.0;
+        // a finished words challenge always covers the whole text, a timed or failed one only what
+        // was typed before it ended. Words that were skipped partway through don't count
+        let word_count = match self.mode {
+            ChallengeMode::Words(_)
+            | ChallengeMode::Quote(_)
+            | ChallengeMode::Custom(_)
+            | ChallengeMode::Adaptive(_)
+                if self.failure.is_none() =>
+            {
+                self.completed_word_count(self.text_length)
+            }
+            _ => self.completed_word_count(self.current_pos),
+        };
 
-        rounded_wpm
+        let wpm = word_count as f32 / challenge_time_mins;
+        (wpm * 10.0).round() / 10.0
     }
 
     // requests the challenge to poll the current wpm and store it
@@ -93,13 +592,23 @@ impl WordsChallengeModel {
             panic!("should not be asked to poll wpm on a finished test");
         }
 
+        let sampled_keystrokes: usize = self.running_keystrokes.iter().sum();
+        let sampled_errors: usize = self.running_errors.iter().sum();
+        self.running_keystrokes.push(self.keystroke_count - sampled_keystrokes);
+        self.running_errors.push(self.error_count - sampled_errors);
+
         if self.start_time.is_none() {
             self.running_wpm.push(0.0);
             return self;
         }
 
-        let completed_substring = &self.text[..=self.current_pos];
-        let completed_words = completed_substring.chars().filter(|c| c == &' ').count();
+        let completed_words = self
+            .text
+            .chars()
+            .take(self.current_pos + 1)
+            .filter(|c| c == &' ')
+            .count()
+            - self.skipped_word_count(self.current_pos);
 
         if completed_words == 0 {
             self.running_wpm.push(0.0);
@@ -118,18 +627,102 @@ impl WordsChallengeModel {
         self
     }
 
-    // The percentage accuracy of the test, rounded to 1 decimal place
+    // pairs up the samples taken each second, raw wpm counts every character typed in that second
+    // as a fifth of a word whether it was correct or not
+    fn wpm_samples(&self) -> Vec<WpmSample> {
+        self.running_wpm
+            .iter()
+            .zip(&self.running_keystrokes)
+            .zip(&self.running_errors)
+            .map(|((wpm, keystrokes), errors)| WpmSample {
+                wpm: *wpm,
+                raw_wpm: *keystrokes as f32 / 5.0 * 60.0,
+                errors: *errors,
+            })
+            .collect()
+    }
+
+    // the number of characters reached so far, letters that were typed incorrectly then deleted
+    // still count as reached
+    fn typed_length(&self) -> usize {
+        let furthest_incorrect = self.incorrect_indices.iter().max().map_or(0, |index| index + 1);
+        self.current_pos.max(furthest_incorrect)
+    }
+
+    fn extra_letter_count(&self) -> usize {
+        self.extra_letters.values().map(|letters| letters.chars().count()).sum()
+    }
+
+    // letters typed incorrectly, missed by skipping a word or typed past the end of a word, a
+    // letter typed incorrectly and later skipped only counts once
+    fn incorrect_character_count(&self) -> usize {
+        self.incorrect_indices.union(&self.missed_indices).count() + self.extra_letter_count()
+    }
+
+    // The number of words finished before the given position without being skipped partway
+    // through. A word is finished once the space after it is reached, the last word once the end
+    // of the text is
+    fn completed_word_count(&self, before: usize) -> usize {
+        let mut completed_words = 0;
+        let mut word_skipped = false;
+        for (index, char) in self.text.chars().take(before).enumerate() {
+            if char == ' ' {
+                completed_words += !word_skipped as usize;
+                word_skipped = false;
+            } else if self.missed_indices.contains(&index) {
+                word_skipped = true;
+            }
+        }
+
+        let is_last_word_reached = before >= self.text_length && !self.text.ends_with(' ');
+        completed_words + (is_last_word_reached && !word_skipped) as usize
+    }
+
+    // the number of words before the given position that were skipped partway through
+    fn skipped_word_count(&self, before: usize) -> usize {
+        if self.missed_indices.is_empty() {
+            return 0;
+        }
+
+        let mut skipped_words = 0;
+        let mut word_skipped = false;
+        for (index, char) in self.text.chars().take(before).enumerate() {
+            if char == ' ' {
+                skipped_words += word_skipped as usize;
+                word_skipped = false;
+            } else if self.missed_indices.contains(&index) {
+                word_skipped = true;
+            }
+        }
+
+        skipped_words + word_skipped as usize
+    }
+
+    // The percentage accuracy of the test over the characters reached so far, rounded to 1
+    // decimal place. Extra letters count as incorrect characters on top of those in the text.
+    // When stopping on errors every letter is right in the end, so instead it's the percentage of
+    // key presses that were right
     pub fn accuracy_percent(&self) -> f32 {
-        if self.incorrect_indices.is_empty() {
+        if self.stop_on_error != StopOnError::Off {
+            if self.keystroke_count == 0 {
+                return 100.0;
+            }
+
+            let correct_keystrokes = (self.keystroke_count - self.error_count) as f32;
+            let accuracy = correct_keystrokes / self.keystroke_count as f32 * 100.0;
+            return (accuracy * 10.0).round() / 10.0;
+        }
+
+        let incorrect_characters = self.incorrect_character_count();
+        if incorrect_characters == 0 {
             return 100.0;
         }
 
-        let correct_letters = (self.text_length - self.incorrect_indices.len()) as f32;
-        let accuracy = correct_letters / (self.text_length as f32);
+        let typed_length = self.typed_length() + self.extra_letter_count();
+        let correct_letters = (typed_length - incorrect_characters) as f32;
+        let accuracy = correct_letters / (typed_length as f32);
         let as_percent = accuracy * 100.0;
-        let rounded_accuracy = (as_percent * 10.0).round() / 10.0;
-
-        rounded_accuracy
+        (as_percent * 10.0).round() / 10.0
     }
 
     fn handle_character(self, input_char: char) -> Self {
@@ -137,31 +730,67 @@ impl WordsChallengeModel {
             panic!("challenge should not be handling input after finishing");
         }
 
-        // the final letter was previously incorrect, it must be corrected or skipped
-        // it will never be counted as correct
-        if self.current_pos == self.text_length {
-            // pressing space should end challenge
-            if input_char == ' ' {
-                return Self {
-                    finished: true,
-                    end_time: Some(Instant::now()),
-                    ..self
-                };
+        let expected_char = self.text.chars().nth(self.current_pos);
+
+        let must_stop = match self.stop_on_error {
+            StopOnError::Off => false,
+            StopOnError::Letter => expected_char != Some(input_char),
+            // space partway through a word is held back as well as after a word with mistakes,
+            // though it's still ignored at the start of a word like it always is
+            StopOnError::Word => {
+                input_char == ' '
+                    && (expected_char.is_some_and(|char| char != ' ') && !self.is_at_word_start()
+                        || self.word_has_mistakes(self.current_pos))
             }
+        };
+        if must_stop {
+            return self.stop_on_mistake();
+        }
 
-            // further incorrect characters should do nothing
-            return self;
+        match (expected_char, input_char) {
+            // the final letter was previously incorrect or had extra letters typed after it, it
+            // will never be counted as correct and pressing space should end challenge
+            (None, ' ') => Self {
+                finished: true,
+                end_time: Some(Instant::now()),
+                ..self
+            },
+            (Some(' '), ' ') => self.type_letter(' ', ' '),
+            // the end of the word has been reached so anything but space is an extra letter
+            (None | Some(' '), _) => self.add_extra_letter(input_char),
+            (Some(_), ' ') => self.skip_word(),
+            (Some(expected_char), _) => self.type_letter(expected_char, input_char),
         }
+    }
 
-        let expected_char = self.text.chars().nth(self.current_pos).unwrap();
+    fn type_letter(self, expected_char: char, input_char: char) -> Self {
         let is_correct = input_char == expected_char;
 
+        // when stopping on mistakes in words the last word has to be right for the challenge to end
+        let is_word_right =
+            self.stop_on_error != StopOnError::Word || !self.word_has_mistakes(self.current_pos);
+        let is_finished = self.current_pos == self.text_length - 1 && is_correct && is_word_right;
+
+        let word_start = self.word_start(self.current_pos);
         let mut incorrect_indices = self.incorrect_indices;
+        let mut mistyped_word_starts = self.mistyped_word_starts;
         if !is_correct {
             incorrect_indices.insert(self.current_pos);
+            mistyped_word_starts.insert(word_start);
+        }
+        let error_count = if is_correct {
+            self.error_count
+        } else {
+            self.error_count + 1
+        };
+
+        let mut uncorrected_indices = self.uncorrected_indices;
+        if is_correct {
+            uncorrected_indices.remove(&self.current_pos);
+        } else {
+            uncorrected_indices.insert(self.current_pos);
         }
 
-        let is_finished = self.current_pos == self.text_length - 1 && is_correct;
         let end_time_if_finished = if is_finished {
             Some(Instant::now())
         } else {
@@ -170,25 +799,138 @@ impl WordsChallengeModel {
 
         Self {
             current_pos: self.current_pos + 1,
+            uncorrected_indices,
             finished: is_finished,
             incorrect_indices,
+            mistyped_word_starts,
+            end_time: end_time_if_finished,
+            keystroke_count: self.keystroke_count + 1,
+            error_count,
+            ..self
+        }
+    }
+
+    // the caret stays where it is, waiting for the space, while extra letters pile up before it
+    fn add_extra_letter(mut self, input_char: char) -> Self {
+        let extra_letters = self.extra_letters.entry(self.current_pos).or_default();
+        if extra_letters.chars().count() >= MAX_EXTRA_LETTERS {
+            return self;
+        }
+        extra_letters.push(input_char);
+        let word_start = self.word_start(self.current_pos);
+        self.mistyped_word_starts.insert(word_start);
+
+        Self {
+            keystroke_count: self.keystroke_count + 1,
+            error_count: self.error_count + 1,
+            ..self
+        }
+    }
+
+    // Space partway through a word jumps to the start of the next one and the rest of the word is
+    // missed, skipping the last word finishes the challenge. Space at the start of a word does
+    // nothing so a doubled space doesn't lose a whole word
+    fn skip_word(mut self) -> Self {
+        if self.is_at_word_start() {
+            return self;
+        }
+
+        let word_end = self
+            .text
+            .chars()
+            .skip(self.current_pos)
+            .position(|char| char == ' ')
+            .map_or(self.text_length, |word_length| self.current_pos + word_length);
+        self.missed_indices.extend(self.current_pos..word_end);
+        let word_start = self.word_start(self.current_pos);
+        self.mistyped_word_starts.insert(word_start);
+
+        let is_finished = word_end == self.text_length;
+        let end_time_if_finished = if is_finished {
+            Some(Instant::now())
+        } else {
+            None
+        };
+
+        Self {
+            current_pos: (word_end + 1).min(self.text_length),
+            finished: is_finished,
             end_time: end_time_if_finished,
+            keystroke_count: self.keystroke_count + 1,
+            error_count: self.error_count + 1,
             ..self
         }
     }
 
-    fn handle_backspace(self) -> Self {
+    // the caret stays where it is after a wrong key when stopping on errors, though the mistake
+    // still counts against the accuracy
+    fn stop_on_mistake(mut self) -> Self {
+        self.uncorrected_indices.insert(self.current_pos);
+        if self.stop_on_error == StopOnError::Letter {
+            self.incorrect_indices.insert(self.current_pos);
+        }
+        let word_start = self.word_start(self.current_pos);
+        self.mistyped_word_starts.insert(word_start);
+
         Self {
-            current_pos: self.current_pos.saturating_sub(1),
+            keystroke_count: self.keystroke_count + 1,
+            error_count: self.error_count + 1,
             ..self
         }
     }
+
+    fn is_at_word_start(&self) -> bool {
+        self.current_pos == 0 || self.text.chars().nth(self.current_pos - 1) == Some(' ')
+    }
+
+    // where the word the position is in starts, a space belongs to the word before it
+    fn word_start(&self, position: usize) -> usize {
+        self.text
+            .chars()
+            .take(position)
+            .enumerate()
+            .filter(|(_, char)| *char == ' ')
+            .last()
+            .map_or(0, |(index, _)| index + 1)
+    }
+
+    // whether the word ending at the given position has letters still typed wrong or extra letters
+    // on the end of it
+    fn word_has_mistakes(&self, word_end: usize) -> bool {
+        let word_start = self.word_start(word_end);
+
+        self.extra_letters.contains_key(&word_end)
+            || (word_start..word_end).any(|index| self.uncorrected_indices.contains(&index))
+    }
+
+    fn handle_backspace(mut self) -> Self {
+        self.uncorrected_indices.remove(&self.current_pos);
+
+        // extra letters go before the caret moves back into the word
+        if let Some(extra_letters) = self.extra_letters.get_mut(&self.current_pos) {
+            extra_letters.pop();
+            if extra_letters.is_empty() {
+                self.extra_letters.remove(&self.current_pos);
+            }
+            return self;
+        }
+
+        // backing over the space after a skipped word goes back to where it was skipped from
+        let mut current_pos = self.current_pos.saturating_sub(1);
+        while current_pos > 0 && self.missed_indices.remove(&(current_pos - 1)) {
+            current_pos -= 1;
+        }
+        self.uncorrected_indices.retain(|index| *index < current_pos);
+
+        Self { current_pos, ..self }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::time::{Duration, Instant};
 
+    use crate::features::words_challenge::word_generator::WordBand;
     use super::*;
 
     fn model_with_text(text: impl ToString) -> WordsChallengeModel {
@@ -196,15 +938,33 @@ mod tests {
         let text_length = text.chars().count();
         let text_word_count = text.split_whitespace().count();
         WordsChallengeModel {
+            mode: ChallengeMode::Words(text_word_count),
+            word_generator: WordGenerator::new(WordBand::Top200, 0),
+            quote: None,
+            adaptive_letters: None,
             text,
             text_length,
             text_word_count,
             current_pos: 0,
             finished: false,
             incorrect_indices: HashSet::new(),
+            extra_letters: HashMap::new(),
+            missed_indices: HashSet::new(),
+            uncorrected_indices: HashSet::new(),
+            mistyped_word_starts: BTreeSet::new(),
             start_time: None,
             end_time: None,
+            keystroke_count: 0,
+            error_count: 0,
             running_wpm: Vec::new(),
+            running_keystrokes: Vec::new(),
+            running_errors: Vec::new(),
+            keystrokes: Vec::new(),
+            ghost: None,
+            backspace_policy: BackspacePolicy::default(),
+            stop_on_error: StopOnError::default(),
+            fail_conditions: FailConditions::default(),
+            failure: None,
         }
     }
 
@@ -213,15 +973,33 @@ mod tests {
         let text_length = text.chars().count();
         let text_word_count = text.split_whitespace().count();
         WordsChallengeModel {
+            mode: ChallengeMode::Words(text_word_count),
+            word_generator: WordGenerator::new(WordBand::Top200, 0),
+            quote: None,
+            adaptive_letters: None,
             text,
             text_length,
             text_word_count,
             current_pos: pos,
             finished: false,
             incorrect_indices: HashSet::new(),
+            extra_letters: HashMap::new(),
+            missed_indices: HashSet::new(),
+            uncorrected_indices: HashSet::new(),
+            mistyped_word_starts: BTreeSet::new(),
             start_time: None,
             end_time: None,
+            keystroke_count: 0,
+            error_count: 0,
             running_wpm: Vec::new(),
+            running_keystrokes: Vec::new(),
+            running_errors: Vec::new(),
+            keystrokes: Vec::new(),
+            ghost: None,
+            backspace_policy: BackspacePolicy::default(),
+            stop_on_error: StopOnError::default(),
+            fail_conditions: FailConditions::default(),
+            failure: None,
         }
     }
 
@@ -247,12 +1025,45 @@ mod tests {
     }
 
     #[test]
-    fn incorrect_slace_advances_challenge() {
+    fn space_partway_through_word_skips_to_next_word() {
         let model = model_with_text_and_pos("space test", 2);
         let result = model.handle_challenge_input(KeyCode::Char(' '));
+        assert_eq!(result.current_pos, 6);
+        assert_eq!(result.missed_indices, HashSet::from([2, 3, 4]));
+    }
+
+    #[test]
+    fn space_at_start_of_word_does_nothing() {
+        let model = model_with_text_and_pos("space test", 6);
+        let result = model.handle_challenge_input(KeyCode::Char(' '));
+        assert_eq!(result.current_pos, 6);
+        assert!(result.missed_indices.is_empty());
+    }
+
+    #[test]
+    fn letters_past_end_of_word_are_extra() {
+        let model = model_with_text_and_pos("ab cd", 2);
+        let result = model
+            .handle_challenge_input(KeyCode::Char('x'))
+            .handle_challenge_input(KeyCode::Char('y'));
+
+        assert_eq!(result.current_pos, 2);
+        assert_eq!(result.extra_letters, HashMap::from([(2, "xy".to_string())]));
+
+        let result = result.handle_challenge_input(KeyCode::Char(' '));
         assert_eq!(result.current_pos, 3);
     }
 
+    #[test]
+    fn extra_letters_stop_at_the_limit() {
+        let mut model = model_with_text_and_pos("ab cd", 2);
+        for _ in 0..MAX_EXTRA_LETTERS + 5 {
+            model = model.handle_challenge_input(KeyCode::Char('x'));
+        }
+
+        assert_eq!(model.extra_letters[&2].len(), MAX_EXTRA_LETTERS);
+    }
+
     #[test]
     fn backspace_at_start_does_nothing() {
         let model = model_with_text("test");
@@ -268,22 +1079,296 @@ mod tests {
     }
 
     #[test]
-    fn correct_final_character_finished_challenge() {
-        let model = model_with_text_and_pos("test", 3);
-        let result = model.handle_challenge_input(KeyCode::Char('t'));
-        assert_eq!(result.finished, true);
+    fn backspace_within_word_stops_at_start_of_word() {
+        let model = model_with_text_and_pos("space test", 7)
+            .with_backspace_policy(BackspacePolicy::WithinWord)
+            .handle_challenge_input(KeyCode::Backspace);
+        assert_eq!(model.current_pos, 6);
+
+        let result = model.handle_challenge_input(KeyCode::Backspace);
+        assert_eq!(result.current_pos, 6);
+        assert_eq!(result.keystrokes.len(), 1);
     }
 
     #[test]
-    fn incorrect_final_character_does_not_finish_challenge() {
-        let model = model_with_text_and_pos("test", 3);
-        let result = model.handle_challenge_input(KeyCode::Char('x'));
-        assert_eq!(result.current_pos, 4);
-        assert_eq!(result.finished, false);
+    fn backspace_removes_extra_letters_before_moving_back() {
+        let model = model_with_text_and_pos("ab cd", 2)
+            .handle_challenge_input(KeyCode::Char('x'))
+            .handle_challenge_input(KeyCode::Char('y'))
+            .handle_challenge_input(KeyCode::Backspace);
+        assert_eq!(model.current_pos, 2);
+        assert_eq!(model.extra_letters[&2], "x");
+
+        let result = model
+            .handle_challenge_input(KeyCode::Backspace)
+            .handle_challenge_input(KeyCode::Backspace);
+        assert_eq!(result.current_pos, 1);
+        assert!(result.extra_letters.is_empty());
+    }
+
+    #[test]
+    fn backspace_after_skipped_word_goes_back_to_where_it_was_skipped() {
+        let result = model_with_text_and_pos("space test", 2)
+            .handle_challenge_input(KeyCode::Char(' '))
+            .handle_challenge_input(KeyCode::Backspace);
+
+        assert_eq!(result.current_pos, 2);
+        assert!(result.missed_indices.is_empty());
+    }
+
+    #[test]
+    fn stop_on_letter_keeps_caret_on_wrong_key() {
+        let model = model_with_text("dog")
+            .with_stop_on_error(StopOnError::Letter)
+            .handle_challenge_input(KeyCode::Char('x'));
+        assert_eq!(model.current_pos, 0);
+        assert_eq!(model.error_count, 1);
+        assert_eq!(model.keystrokes.len(), 1);
+
+        let result = model.handle_challenge_input(KeyCode::Char('d'));
+        assert_eq!(result.current_pos, 1);
+    }
+
+    #[test]
+    fn stop_on_letter_holds_space_partway_through_word() {
+        let result = model_with_text("ab cd")
+            .with_stop_on_error(StopOnError::Letter)
+            .handle_challenge_input(KeyCode::Char('a'))
+            .handle_challenge_input(KeyCode::Char(' '));
+
+        assert_eq!(result.current_pos, 1);
+        assert!(result.missed_indices.is_empty());
+    }
+
+    #[test]
+    fn stop_on_error_counts_accuracy_by_key_presses() {
+        let result = model_with_text("dog")
+            .with_stop_on_error(StopOnError::Letter)
+            .handle_challenge_input(KeyCode::Char('x'))
+            .handle_challenge_input(KeyCode::Char('d'))
+            .handle_challenge_input(KeyCode::Char('o'))
+            .handle_challenge_input(KeyCode::Char('g'));
+
+        assert!(result.finished);
+        assert_eq!(result.accuracy_percent(), 75.0);
+    }
+
+    #[test]
+    fn stop_on_word_holds_space_until_word_is_right() {
+        let model = model_with_text("ab cd")
+            .with_stop_on_error(StopOnError::Word)
+            .handle_challenge_input(KeyCode::Char('a'))
+            .handle_challenge_input(KeyCode::Char('x'))
+            .handle_challenge_input(KeyCode::Char(' '));
+        assert_eq!(model.current_pos, 2);
+
+        let result = model
+            .handle_challenge_input(KeyCode::Backspace)
+            .handle_challenge_input(KeyCode::Char('b'))
+            .handle_challenge_input(KeyCode::Char(' '));
+        assert_eq!(result.current_pos, 3);
+    }
+
+    #[test]
+    fn stop_on_word_holds_space_after_extra_letters() {
+        let model = model_with_text_and_pos("ab cd", 2)
+            .with_stop_on_error(StopOnError::Word)
+            .handle_challenge_input(KeyCode::Char('x'))
+            .handle_challenge_input(KeyCode::Char(' '));
+        assert_eq!(model.current_pos, 2);
+
+        let result = model
+            .handle_challenge_input(KeyCode::Backspace)
+            .handle_challenge_input(KeyCode::Char(' '));
+        assert_eq!(result.current_pos, 3);
+    }
+
+    #[test]
+    fn stop_on_word_only_finishes_once_last_word_is_right() {
+        let model = model_with_text("ab")
+            .with_stop_on_error(StopOnError::Word)
+            .handle_challenge_input(KeyCode::Char('x'))
+            .handle_challenge_input(KeyCode::Char('b'))
+            .handle_challenge_input(KeyCode::Char(' '));
+        assert_eq!(model.current_pos, 2);
+        assert!(!model.finished);
+
+        let result = model
+            .handle_challenge_input(KeyCode::Backspace)
+            .handle_challenge_input(KeyCode::Backspace)
+            .handle_challenge_input(KeyCode::Char('a'))
+            .handle_challenge_input(KeyCode::Char('b'));
+        assert!(result.finished);
+    }
+
+    #[test]
+    fn next_character_is_at_the_caret_until_finished() {
+        let model = model_with_text("ab").handle_challenge_input(KeyCode::Char('a'));
+        assert_eq!(model.next_character(), Some('b'));
+
+        let model = model.handle_challenge_input(KeyCode::Char('b'));
+        assert_eq!(model.next_character(), None);
+    }
+
+    #[test]
+    fn wrong_key_is_shown_after_a_mistake() {
+        let model = model_with_text("abc").handle_challenge_input(KeyCode::Char('x'));
+        assert_eq!(model.wrong_key(), Some('x'));
+
+        let model = model.handle_challenge_input(KeyCode::Char('b'));
+        assert_eq!(model.wrong_key(), None);
+    }
+
+    #[test]
+    fn sudden_death_fails_on_first_mistake() {
+        let fail_conditions = FailConditions {
+            sudden_death: true,
+            ..FailConditions::default()
+        };
+        let model = model_with_text("some words")
+            .with_fail_conditions(fail_conditions)
+            .handle_challenge_input(KeyCode::Char('x'));
+
+        assert!(model.finished);
+        assert_eq!(model.failure, Some(FailReason::Mistake));
+        assert_eq!(model.wpm(), 0.0);
+    }
+
+    #[test]
+    fn min_accuracy_waits_for_a_few_characters() {
+        let fail_conditions = FailConditions {
+            min_accuracy: Some(90.0),
+            ..FailConditions::default()
+        };
+        let model = model_with_text("some words to type")
+            .with_fail_conditions(fail_conditions)
+            .handle_challenge_input(KeyCode::Char('x'));
+        assert!(!model.finished);
+
+        let result = "xme words".chars().fold(model, |model, char| {
+            model.handle_challenge_input(KeyCode::Char(char))
+        });
+        assert!(result.finished);
+        assert_eq!(result.failure, Some(FailReason::AccuracyBelow(90.0)));
+    }
+
+    #[test]
+    fn min_wpm_fails_once_typing_is_too_slow_for_long_enough() {
+        let now = Instant::now();
+        let fail_conditions = FailConditions {
+            min_wpm: Some(30.0),
+            min_wpm_seconds: 3,
+            ..FailConditions::default()
+        };
+        let model = || {
+            WordsChallengeModel {
+                start_time: Some(now - Duration::from_secs(3)),
+                ..model_with_text_and_pos("some words to type", 1)
+            }
+            .with_fail_conditions(fail_conditions)
+        };
+
+        let slow = model().poll_wpm().poll_wpm();
+        assert!(!slow.check_fail_conditions(now).finished);
+
+        let result = model().poll_wpm().poll_wpm().poll_wpm().check_fail_conditions(now);
+        assert!(result.finished);
+        assert_eq!(
+            result.failure,
+            Some(FailReason::WpmBelow {
+                wpm: 30.0,
+                seconds: 3
+            })
+        );
+    }
+
+    #[test]
+    fn failing_by_skipping_the_last_word_gives_no_wpm() {
+        let fail_conditions = FailConditions {
+            sudden_death: true,
+            ..FailConditions::default()
+        };
+        let start_time = Instant::now();
+        let model = WordsChallengeModel {
+            start_time: Some(start_time),
+            ..model_with_text("ab")
+        }
+        .with_fail_conditions(fail_conditions)
+        .handle_challenge_input(KeyCode::Char('a'))
+        .handle_challenge_input(KeyCode::Char(' '));
+        let model = WordsChallengeModel {
+            end_time: Some(start_time + Duration::from_secs(6)),
+            ..model
+        };
+
+        assert_eq!(model.failure, Some(FailReason::Mistake));
+        assert_eq!(model.wpm(), 0.0);
+    }
+
+    #[test]
+    fn failed_challenge_counts_words_completed_before_a_skipped_last_word() {
+        let fail_conditions = FailConditions {
+            min_accuracy: Some(90.0),
+            ..FailConditions::default()
+        };
+        let start_time = Instant::now();
+        let model = "ab c ".chars().fold(
+            WordsChallengeModel {
+                start_time: Some(start_time),
+                ..model_with_text("ab cd")
+            }
+            .with_fail_conditions(fail_conditions),
+            |model, char| model.handle_challenge_input(KeyCode::Char(char)),
+        );
+        let model = WordsChallengeModel {
+            end_time: Some(start_time + Duration::from_secs(6)),
+            ..model
+        };
+
+        assert_eq!(model.failure, Some(FailReason::AccuracyBelow(90.0)));
+        assert_eq!(model.wpm(), 10.0);
+    }
+
+    #[test]
+    fn failed_result_records_why() {
+        let fail_conditions = FailConditions {
+            sudden_death: true,
+            ..FailConditions::default()
+        };
+        let result = model_with_text("dog")
+            .with_fail_conditions(fail_conditions)
+            .handle_challenge_input(KeyCode::Char('d'))
+            .handle_challenge_input(KeyCode::Char('a'))
+            .result();
+
+        assert_eq!(result.failure, Some(FailReason::Mistake));
+    }
+
+    #[test]
+    fn backspace_never_does_nothing() {
+        let model = model_with_text_and_pos("test", 2).with_backspace_policy(BackspacePolicy::Never);
+        let result = model.handle_challenge_input(KeyCode::Backspace);
+        assert_eq!(result.current_pos, 2);
+        assert!(result.keystrokes.is_empty());
+    }
+
+    #[test]
+    fn correct_final_character_finished_challenge() {
+        let model = model_with_text_and_pos("test", 3);
+        let result = model.handle_challenge_input(KeyCode::Char('t'));
+        assert!(result.finished);
+    }
+
+    #[test]
+    fn incorrect_final_character_does_not_finish_challenge() {
+        let model = model_with_text_and_pos("test", 3);
+        let result = model.handle_challenge_input(KeyCode::Char('x'));
+        assert_eq!(result.current_pos, 4);
+        assert!(!result.finished);
     }
 
     #[test]
-    fn further_incorrect_characters_are_not_registered() {
+    fn further_characters_after_incorrect_final_character_are_extra() {
         let model = model_with_text_and_pos("test", 3);
         let result = model
             .handle_challenge_input(KeyCode::Char('x'))
@@ -291,7 +1376,8 @@ mod tests {
             .handle_challenge_input(KeyCode::Char('x'));
 
         assert_eq!(result.current_pos, 4);
-        assert_eq!(result.finished, false);
+        assert_eq!(result.extra_letters, HashMap::from([(4, "xx".to_string())]));
+        assert!(!result.finished);
     }
 
     #[test]
@@ -302,16 +1388,17 @@ mod tests {
             .handle_challenge_input(KeyCode::Char(' '));
 
         assert_eq!(result.current_pos, 4);
-        assert_eq!(result.finished, true);
+        assert!(result.finished);
     }
 
     #[test]
-    fn space_as_final_incorrect_letter_does_not_finished_challenge() {
+    fn skipping_final_word_finishes_challenge() {
         let model = model_with_text_and_pos("test", 3);
         let result = model.handle_challenge_input(KeyCode::Char(' '));
 
         assert_eq!(result.current_pos, 4);
-        assert_eq!(result.finished, false);
+        assert_eq!(result.missed_indices, HashSet::from([3]));
+        assert!(result.finished);
     }
 
     #[test]
@@ -400,9 +1487,57 @@ mod tests {
         assert_eq!(result.accuracy_percent(), 66.7);
     }
 
+    #[test]
+    fn extra_and_missed_letters_count_against_accuracy() {
+        let result = model_with_text("dog cat")
+            .handle_challenge_input(KeyCode::Char('d'))
+            .handle_challenge_input(KeyCode::Char('o'))
+            .handle_challenge_input(KeyCode::Char('g'))
+            .handle_challenge_input(KeyCode::Char('s'))
+            .handle_challenge_input(KeyCode::Char(' '))
+            .handle_challenge_input(KeyCode::Char('c'))
+            .handle_challenge_input(KeyCode::Char(' '));
+
+        // 5 right out of 8, with 1 extra letter and 2 missed
+        assert_eq!(result.accuracy_percent(), 62.5);
+
+        let challenge_result = result.result();
+        assert_eq!(challenge_result.correct_characters, 5);
+        assert_eq!(challenge_result.incorrect_characters, 3);
+    }
+
+    #[test]
+    fn skipped_words_do_not_count_towards_wpm() {
+        let start_time = Instant::now();
+        let model = WordsChallengeModel {
+            start_time: Some(start_time),
+            ..model_with_text("one two three")
+        };
+
+        let result = model
+            .handle_challenge_input(KeyCode::Char('o'))
+            .handle_challenge_input(KeyCode::Char(' '))
+            .handle_challenge_input(KeyCode::Char('t'))
+            .handle_challenge_input(KeyCode::Char('w'))
+            .handle_challenge_input(KeyCode::Char('o'))
+            .handle_challenge_input(KeyCode::Char(' '))
+            .handle_challenge_input(KeyCode::Char('t'))
+            .handle_challenge_input(KeyCode::Char(' '));
+        let result = WordsChallengeModel {
+            end_time: Some(start_time + Duration::from_secs(6)),
+            ..result
+        };
+
+        assert!(result.finished);
+        assert_eq!(result.wpm(), 10.0);
+    }
+
     #[test]
     fn challenge_should_only_start_on_first_input() {
-        let model = WordsChallengeModel::generate(3);
+        let model = WordsChallengeModel::new(
+            ChallengeMode::Words(3),
+            WordGenerator::new(WordBand::Top200, 0),
+        );
         assert_eq!(model.start_time, None);
 
         let result = model.handle_challenge_input(KeyCode::Char('a'));
@@ -427,15 +1562,33 @@ mod tests {
         let end_time = start_time + Duration::from_secs(3);
 
         let model = WordsChallengeModel {
+            mode: ChallengeMode::Words(3),
+            word_generator: WordGenerator::new(WordBand::Top200, 0),
+            quote: None,
+            adaptive_letters: None,
             text: "three words long".to_string(),
             text_length: 16,
             text_word_count: 3,
             current_pos: 15,
             finished: true,
             incorrect_indices: HashSet::new(),
+            extra_letters: HashMap::new(),
+            missed_indices: HashSet::new(),
+            uncorrected_indices: HashSet::new(),
+            mistyped_word_starts: BTreeSet::new(),
             start_time: Some(start_time),
             end_time: Some(end_time),
+            keystroke_count: 0,
+            error_count: 0,
             running_wpm: Vec::new(),
+            running_keystrokes: Vec::new(),
+            running_errors: Vec::new(),
+            keystrokes: Vec::new(),
+            ghost: None,
+            backspace_policy: BackspacePolicy::default(),
+            stop_on_error: StopOnError::default(),
+            fail_conditions: FailConditions::default(),
+            failure: None,
         };
 
         let wpm = model.wpm();
@@ -447,18 +1600,379 @@ mod tests {
         let now = Instant::now();
         let start_time = now - Duration::from_secs(2);
         let model = WordsChallengeModel {
+            mode: ChallengeMode::Words(5),
+            word_generator: WordGenerator::new(WordBand::Top200, 0),
+            quote: None,
+            adaptive_letters: None,
             text: "five words long this text".to_string(),
             text_length: 25,
             text_word_count: 5,
             current_pos: 15,
             finished: true,
             incorrect_indices: HashSet::new(),
+            extra_letters: HashMap::new(),
+            missed_indices: HashSet::new(),
+            uncorrected_indices: HashSet::new(),
+            mistyped_word_starts: BTreeSet::new(),
             start_time: Some(start_time),
             end_time: None,
+            keystroke_count: 0,
+            error_count: 0,
             running_wpm: Vec::new(),
+            running_keystrokes: Vec::new(),
+            running_errors: Vec::new(),
+            keystrokes: Vec::new(),
+            ghost: None,
+            backspace_policy: BackspacePolicy::default(),
+            stop_on_error: StopOnError::default(),
+            fail_conditions: FailConditions::default(),
+            failure: None,
         };
 
         let result = model.poll_wpm();
         assert_eq!(result.running_wpm[0], 90.0);
     }
+
+    fn timed_model_with_text(text: impl ToString, seconds: u64) -> WordsChallengeModel {
+        let mode = ChallengeMode::Time(seconds);
+        WordsChallengeModel {
+            mode,
+            ..model_with_text(text)
+        }
+    }
+
+    #[test]
+    fn words_mode_generates_requested_number_of_words() {
+        let model = WordsChallengeModel::new(
+            ChallengeMode::Words(25),
+            WordGenerator::new(WordBand::Top1k, 1),
+        );
+        assert_eq!(model.text.split(' ').count(), 25);
+        assert_eq!(model.text_length, model.text.chars().count());
+    }
+
+    #[test]
+    fn timed_challenge_finishes_when_time_runs_out() {
+        let start_time = Instant::now();
+        let model = WordsChallengeModel {
+            start_time: Some(start_time),
+            ..timed_model_with_text("timed test", 15)
+        };
+
+        let result = model.check_time_limit(start_time + Duration::from_secs(14));
+        assert!(!result.finished);
+
+        let result = result.check_time_limit(start_time + Duration::from_secs(16));
+        assert!(result.finished);
+        assert_eq!(result.end_time, Some(start_time + Duration::from_secs(15)));
+    }
+
+    #[test]
+    fn unstarted_timed_challenge_does_not_finish() {
+        let model = timed_model_with_text("timed test", 15);
+        let result = model.check_time_limit(Instant::now() + Duration::from_secs(60));
+        assert!(!result.finished);
+    }
+
+    #[test]
+    fn input_after_time_runs_out_is_ignored() {
+        let model = WordsChallengeModel {
+            start_time: Some(Instant::now() - Duration::from_secs(20)),
+            ..timed_model_with_text("timed test", 15)
+        };
+
+        let result = model.handle_challenge_input(KeyCode::Char('t'));
+        assert!(result.finished);
+        assert_eq!(result.current_pos, 0);
+    }
+
+    #[test]
+    fn timed_challenge_grows_text_near_the_end() {
+        let model = WordsChallengeModel::new(
+            ChallengeMode::Time(30),
+            WordGenerator::new(WordBand::Top200, 0),
+        );
+        let initial_length = model.text_length;
+        let near_end = WordsChallengeModel {
+            current_pos: initial_length - 1,
+            ..model
+        };
+
+        let result = near_end.handle_challenge_input(KeyCode::Char('x'));
+        assert!(result.text_length > initial_length);
+        assert_eq!(result.text_length, result.text.chars().count());
+        assert!(!result.finished);
+    }
+
+    #[test]
+    fn timed_challenge_wpm_counts_only_typed_words() {
+        let start_time = Instant::now();
+        let model = WordsChallengeModel {
+            current_pos: 11,
+            finished: true,
+            start_time: Some(start_time),
+            end_time: Some(start_time + Duration::from_secs(15)),
+            ..timed_model_with_text("some words that were not reached", 15)
+        };
+
+        assert_eq!(model.wpm(), 8.0);
+    }
+
+    #[test]
+    fn timed_challenge_accuracy_counts_only_typed_characters() {
+        let model = timed_model_with_text("dog cat bird", 15);
+
+        let result = model
+            .handle_challenge_input(KeyCode::Char('d'))
+            .handle_challenge_input(KeyCode::Char('a'))
+            .handle_challenge_input(KeyCode::Char('g'))
+            .handle_challenge_input(KeyCode::Char(' '));
+
+        assert_eq!(result.accuracy_percent(), 75.0);
+    }
+
+    #[test]
+    fn tick_samples_wpm_once_per_elapsed_second() {
+        let now = Instant::now();
+        let model = WordsChallengeModel {
+            start_time: Some(now - Duration::from_millis(2500)),
+            ..model_with_text_and_pos("some words to type", 5)
+        };
+
+        let result = model.tick(now).tick(now).tick(now);
+        assert_eq!(result.running_wpm.len(), 2);
+    }
+
+    #[test]
+    fn tick_does_not_sample_unstarted_challenge() {
+        let model = model_with_text("some words");
+        let result = model.tick(Instant::now());
+        assert!(result.running_wpm.is_empty());
+    }
+
+    #[test]
+    fn tick_finishes_timed_challenge_when_time_runs_out() {
+        let now = Instant::now();
+        let model = WordsChallengeModel {
+            start_time: Some(now - Duration::from_secs(31)),
+            ..timed_model_with_text("timed test", 30)
+        };
+
+        let result = model.tick(now);
+        assert!(result.finished);
+    }
+
+    #[test]
+    fn poll_wpm_at_end_of_text_does_not_panic() {
+        let model = WordsChallengeModel {
+            start_time: Some(Instant::now() - Duration::from_secs(2)),
+            ..model_with_text_and_pos("ab", 2)
+        };
+
+        let result = model.poll_wpm();
+        assert_eq!(result.running_wpm.len(), 1);
+    }
+
+    #[test]
+    fn result_counts_correct_and_incorrect_characters() {
+        let model = model_with_text("dog");
+
+        let result = model
+            .handle_challenge_input(KeyCode::Char('d'))
+            .handle_challenge_input(KeyCode::Char('a'))
+            .handle_challenge_input(KeyCode::Char('g'))
+            .result();
+
+        assert_eq!(result.correct_characters, 2);
+        assert_eq!(result.incorrect_characters, 1);
+        assert_eq!(result.settings.mode, ChallengeMode::Words(1));
+    }
+
+    #[test]
+    fn poll_wpm_records_keystrokes_and_errors_since_last_sample() {
+        let model = WordsChallengeModel {
+            start_time: Some(Instant::now() - Duration::from_secs(1)),
+            ..model_with_text("dog cat")
+        };
+
+        let result = model
+            .handle_challenge_input(KeyCode::Char('d'))
+            .handle_challenge_input(KeyCode::Char('x'))
+            .poll_wpm()
+            .handle_challenge_input(KeyCode::Char('g'))
+            .poll_wpm();
+
+        assert_eq!(result.running_keystrokes, vec![2, 1]);
+        assert_eq!(result.running_errors, vec![1, 0]);
+
+        let samples = result.wpm_samples();
+        assert_eq!(samples[0].raw_wpm, 24.0);
+        assert_eq!(samples[1].errors, 0);
+    }
+
+    #[test]
+    fn keystrokes_are_logged_with_expected_characters_and_positions() {
+        let result = model_with_text("dog")
+            .handle_challenge_input(KeyCode::Char('d'))
+            .handle_challenge_input(KeyCode::Char('x'))
+            .handle_challenge_input(KeyCode::Backspace)
+            .handle_challenge_input(KeyCode::Char('o'));
+
+        let logged: Vec<(KeystrokeKey, Option<char>, usize)> = result
+            .keystrokes
+            .iter()
+            .map(|keystroke| (keystroke.key, keystroke.expected, keystroke.position))
+            .collect();
+
+        assert_eq!(
+            logged,
+            vec![
+                (KeystrokeKey::Character('d'), Some('d'), 0),
+                (KeystrokeKey::Character('x'), Some('o'), 1),
+                (KeystrokeKey::Backspace, Some('g'), 2),
+                (KeystrokeKey::Character('o'), Some('o'), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn keystroke_times_are_relative_to_start() {
+        let model = WordsChallengeModel {
+            start_time: Some(Instant::now() - Duration::from_secs(2)),
+            ..model_with_text("dog")
+        };
+
+        let result = model.handle_challenge_input(KeyCode::Char('d'));
+
+        assert!(result.keystrokes[0].time >= Duration::from_secs(2));
+    }
+
+    #[test]
+    fn first_keystroke_is_at_start() {
+        let result = model_with_text("dog").handle_challenge_input(KeyCode::Char('d'));
+        assert_eq!(result.keystrokes[0].time, Duration::ZERO);
+    }
+
+    #[test]
+    fn other_keys_are_not_logged() {
+        let result = model_with_text("dog").handle_challenge_input(KeyCode::Left);
+        assert!(result.keystrokes.is_empty());
+    }
+
+    #[test]
+    fn replaying_keystrokes_gives_same_state_as_typing_them() {
+        let typed = model_with_text("dog cat")
+            .handle_challenge_input(KeyCode::Char('d'))
+            .handle_challenge_input(KeyCode::Char('x'))
+            .handle_challenge_input(KeyCode::Backspace)
+            .handle_challenge_input(KeyCode::Char('o'));
+
+        let replayed = typed
+            .keystrokes
+            .iter()
+            .fold(model_with_text("dog cat"), |model, keystroke| {
+                model.replay_keystroke(keystroke)
+            });
+
+        assert_eq!(replayed.current_pos, typed.current_pos);
+        assert_eq!(replayed.incorrect_indices, typed.incorrect_indices);
+    }
+
+    #[test]
+    fn words_stay_mistyped_after_the_mistake_is_put_right() {
+        let typed = "tx\x08he foxx\x08 jum over"
+            .chars()
+            .fold(model_with_text("the fox jumps over the dog"), |model, char| {
+                let keycode = match char {
+                    '\x08' => KeyCode::Backspace,
+                    char => KeyCode::Char(char),
+                };
+                model.handle_challenge_input(keycode)
+            });
+
+        assert_eq!(typed.mistyped_words(), vec!["the", "fox", "jumps"]);
+    }
+
+    #[test]
+    fn word_mistyped_in_two_places_is_only_listed_once() {
+        let typed = "thx fox jumps over tha dox"
+            .chars()
+            .fold(model_with_text("the fox jumps over the dog"), |model, char| {
+                model.handle_challenge_input(KeyCode::Char(char))
+            });
+
+        assert_eq!(typed.mistyped_words(), vec!["the", "dog"]);
+    }
+
+    #[test]
+    fn adaptive_lesson_keeps_its_text_for_typing_again() {
+        let finished = WordsChallengeModel::from_adaptive_text("tin".to_string())
+            .handle_challenge_input(KeyCode::Char('t'))
+            .handle_challenge_input(KeyCode::Char('i'))
+            .handle_challenge_input(KeyCode::Char('n'));
+        let result = finished.result();
+
+        assert_eq!(result.settings.mode, ChallengeMode::Adaptive(1));
+        assert_eq!(result.custom_text.as_deref(), Some("tin"));
+
+        let again = WordsChallengeModel::same_text_as(&result);
+        assert_eq!(again.mode(), ChallengeMode::Adaptive(1));
+        assert_eq!(again.text, "tin");
+    }
+
+    // a ghost of a run that typed the letters of "dog" at the given times
+    fn ghost_typing(letters: &[(char, u64)]) -> Option<Ghost> {
+        let keystrokes = letters
+            .iter()
+            .enumerate()
+            .map(|(index, (letter, millis))| Keystroke {
+                key: KeystrokeKey::Character(*letter),
+                expected: Some(*letter),
+                position: index,
+                time: Duration::from_millis(*millis),
+            })
+            .collect();
+
+        Ghost::from_result(&ChallengeResult {
+            settings: ChallengeSettings::new(ChallengeMode::Custom(1), WordBand::Top200),
+            seed: 0,
+            wpm: 60.0,
+            accuracy: 100.0,
+            duration: Duration::from_secs(1),
+            correct_characters: 3,
+            incorrect_characters: 0,
+            completed_at: Local::now(),
+            wpm_samples: Vec::new(),
+            keystrokes,
+            quote_id: None,
+            custom_text: Some("dog".to_string()),
+            stop_on_error: StopOnError::Off,
+            failure: None,
+            mistyped_words: Vec::new(),
+        })
+    }
+
+    #[test]
+    fn ghost_waits_at_start_until_challenge_starts() {
+        let ghost = ghost_typing(&[('d', 0)]);
+        let model = model_with_text("dog").with_ghost(ghost);
+
+        assert_eq!(model.ghost_position(), Some(0));
+    }
+
+    #[test]
+    fn ghost_lead_is_negative_when_behind() {
+        let ghost = ghost_typing(&[('d', 0), ('o', 100)]);
+        let model = WordsChallengeModel {
+            start_time: Some(Instant::now() - Duration::from_secs(1)),
+            ..model_with_text("dog").with_ghost(ghost)
+        };
+
+        assert_eq!(model.ghost_lead(), Some(-2));
+    }
+
+    #[test]
+    fn no_ghost_lead_without_ghost() {
+        assert_eq!(model_with_text("dog").ghost_lead(), None);
+    }
 }
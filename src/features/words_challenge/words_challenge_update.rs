@@ -1,6 +1,13 @@
-use crate::tui::{app_model::AppModel, app_page::AppPage, message::Message};
+use std::time::Instant;
+
+use crate::{
+    features::results::results_model::ResultsModel,
+    tui::{app_model::AppModel, app_page::AppPage, message::Message},
+};
 use color_eyre::Result;
 
+use super::words_challenge_model::WordsChallengeModel;
+
 pub fn words_challenge_update(
     model: AppModel,
     message: Message,
@@ -14,12 +21,49 @@ pub fn words_challenge_update(
     };
 
     match message {
-        Message::ChallengeLetterInput(keycode) => {
-            let new_model = AppModel {
-                app_page: AppPage::WordsChallenge(challenge.handle_challenge_input(keycode)),
+        // a finished challenge no longer takes input
+        Message::ChallengeLetterInput(_) if challenge.is_finished() => Ok((
+            AppModel {
+                app_page: AppPage::WordsChallenge(challenge),
                 ..model
-            };
-            Ok((new_model, None))
+            },
+            None,
+        )),
+        Message::ChallengeLetterInput(keycode) => {
+            let keycode = model.config.keyboard_layout.emulate(keycode);
+            let new_challenge = challenge.handle_challenge_input(keycode);
+            let next_message = finished_message(&new_challenge);
+            Ok((
+                AppModel {
+                    app_page: AppPage::WordsChallenge(new_challenge),
+                    ..model
+                },
+                next_message,
+            ))
+        }
+        Message::Tick => {
+            let new_challenge = challenge.tick(Instant::now());
+            let next_message = finished_message(&new_challenge);
+            Ok((
+                AppModel {
+                    app_page: AppPage::WordsChallenge(new_challenge),
+                    ..model
+                },
+                next_message,
+            ))
+        }
+        Message::ChallengeFinished => {
+            let result = challenge.result();
+            let id = model.database.save_result(&result)?;
+            let previous_best = model.database.personal_best_before(&result, id)?;
+
+            Ok((
+                AppModel {
+                    app_page: AppPage::Results(ResultsModel::new(result, previous_best)),
+                    ..model
+                },
+                None,
+            ))
         }
         _ => Ok((
             AppModel {
@@ -30,3 +74,12 @@ pub fn words_challenge_update(
         )),
     }
 }
+
+// once the challenge has finished the results should be shown
+fn finished_message(challenge: &WordsChallengeModel) -> Option<Message> {
+    if challenge.is_finished() {
+        Some(Message::ChallengeFinished)
+    } else {
+        None
+    }
+}
@@ -2,20 +2,56 @@ use ratatui::layout::Flex;
 use ratatui::prelude::*;
 use ratatui::Frame;
 
+use crate::config::app_config::AppConfig;
+use crate::features::keyboard::keyboard_widget::{KEYBOARD_HEIGHT, KEYBOARD_WIDTH};
+
+use super::challenge_hud_widget::ChallengeHudWidget;
+use super::on_screen_keyboard_widget::OnScreenKeyboardWidget;
 use super::words_challenge_model::WordsChallengeModel;
-use super::words_challenge_widget::WordsChallengeWidget;
+use super::words_challenge_widget::{WordsChallengeWidget, VISIBLE_LINES};
 
 // Job of this function is to take the whole frame (whole screen) and build a centered rectangle of
-// 80xN pixels centered vertically and horizontally
-// Where N is an appropriate height for the number of words with wrapping
-// TODO: the wrapping bit
-pub fn words_challenge_view(challenge: &WordsChallengeModel, frame: &mut Frame) {
-    let vertical_center = Layout::vertical([Constraint::Length(1)]).flex(Flex::Center).split(frame.area())[0];
-    let horizontal_center = Layout::horizontal([Constraint::Length(80)]).flex(Flex::Center).split(vertical_center);
+// 80xN pixels centered vertically and horizontally, with the hud sat on the line above it
+// Where N is the number of lines of text the challenge widget shows at once, the on-screen
+// keyboard goes underneath when it's turned on
+pub fn words_challenge_view(challenge: &WordsChallengeModel, config: &AppConfig, frame: &mut Frame) {
+    let keyboard_height = if config.show_keyboard { KEYBOARD_HEIGHT } else { 0 };
+
+    let horizontal_center = Layout::horizontal([Constraint::Length(80)]).flex(Flex::Center).split(frame.area())[0];
+    let [hud_area, _, text_area, _, keyboard_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Length(VISIBLE_LINES),
+        Constraint::Length(keyboard_height.min(1)),
+        Constraint::Length(keyboard_height),
+    ])
+    .flex(Flex::Center)
+    .areas(horizontal_center);
+
+    let hud_widget = ChallengeHudWidget {
+        challenge_model: challenge,
+        theme: &config.theme,
+    };
 
     let challenge_widget = WordsChallengeWidget {
         challenge_model: challenge,
+        caret_style: config.caret_style,
+        theme: &config.theme,
     };
 
-    frame.render_widget(challenge_widget, frame.area());
+    frame.render_widget(hud_widget, hud_area);
+    frame.render_widget(challenge_widget, text_area);
+
+    if config.show_keyboard {
+        let keyboard_widget = OnScreenKeyboardWidget {
+            challenge_model: challenge,
+            layout: &config.keyboard_layout,
+            theme: &config.theme,
+        };
+        let keyboard_area = Layout::horizontal([Constraint::Length(KEYBOARD_WIDTH)])
+            .flex(Flex::Center)
+            .split(keyboard_area)[0];
+
+        frame.render_widget(keyboard_widget, keyboard_area);
+    }
 }
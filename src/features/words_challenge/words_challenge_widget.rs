@@ -1,31 +1,94 @@
-use std::iter;
-
 use ratatui::prelude::*;
 
-use super::{character_widget::CharacterWidget, words_challenge_model::WordsChallengeModel};
+use crate::config::theme::Theme;
+
+use super::{
+    caret_style::CaretStyle,
+    character_widget::{CharacterStyle, CharacterWidget},
+    text_wrap::wrap_text,
+    words_challenge_model::WordsChallengeModel,
+};
 
 pub struct WordsChallengeWidget<'a> {
     pub challenge_model: &'a WordsChallengeModel,
+    pub caret_style: CaretStyle,
+    pub theme: &'a Theme,
 }
 
+// the number of lines of text shown at once
+pub const VISIBLE_LINES: u16 = 3;
+
 // Job of this widget is to fill up the given area with coloured letters from the
 // model state with nice wrapping
-// Where N is gonna be the number of lines accounting for text wrapping
-// N and wrapping is TODO until later
+// The text is wrapped between words to the width of the area and only a few lines are shown,
+// scrolling down as the caret moves so it stays on the middle line once past the first
 impl Widget for WordsChallengeWidget<'_> {
     fn render(self, area: Rect, buf: &mut Buffer)
     where
         Self: Sized,
     {
-        // TODO: make this have as many rows as needed to allow for wrapping
-        let line_layout = Layout::vertical([Constraint::Length(1)]).split(area);
+        let letters = drawn_letters(self.challenge_model);
+        let drawn_text: String = letters.iter().map(|(char, _)| char).collect();
+        let lines = wrap_text(&drawn_text, area.width as usize);
 
-        let character_constraints = iter::repeat(Constraint::Length(1)).take(self.challenge_model.text_length);
-        let text_layout = Layout::horizontal(character_constraints).split(line_layout[0]);
+        // the caret is past the last line once the text is finished
+        let caret_index = letters
+            .iter()
+            .position(|(_, text_index)| *text_index == Some(self.challenge_model.current_pos));
+        let caret_line = caret_index
+            .and_then(|caret_index| lines.iter().position(|line| line.contains(&caret_index)))
+            .unwrap_or(lines.len() - 1);
+        let visible_lines = area.height.min(VISIBLE_LINES) as usize;
+        let first_line = caret_line
+            .saturating_sub(1)
+            .min(lines.len().saturating_sub(visible_lines));
 
-        for letter_index in 0..self.challenge_model.text_length {
-            let character_widget = CharacterWidget::get_widget_from_model(&self.challenge_model, letter_index);
-            character_widget.render(text_layout[letter_index], buf);
+        // each letter gets a one cell rect of its own, solving a layout with a constraint per
+        // letter gets far too slow once the text is more than a few words long
+        for (row, line) in lines
+            .into_iter()
+            .skip(first_line)
+            .take(visible_lines)
+            .enumerate()
+        {
+            for (column, letter_index) in line.enumerate() {
+                let letter_area = Rect::new(area.x + column as u16, area.y + row as u16, 1, 1);
+                let character_widget = match letters[letter_index] {
+                    (_, Some(text_index)) => CharacterWidget::get_widget_from_model(
+                        self.challenge_model,
+                        text_index,
+                        self.caret_style,
+                        self.theme,
+                    ),
+                    (char, None) => CharacterWidget {
+                        char,
+                        style: CharacterStyle::Extra,
+                        theme: self.theme,
+                    },
+                };
+                character_widget.render(letter_area, buf);
+            }
         }
     }
 }
+
+// every letter in the order it's drawn, each with its position in the text or nothing for an
+// extra letter typed past the end of a word, which sit between the word and the space after it
+fn drawn_letters(model: &WordsChallengeModel) -> Vec<(char, Option<usize>)> {
+    let extra_letters_at = |index: usize| {
+        model
+            .extra_letters
+            .get(&index)
+            .into_iter()
+            .flat_map(|letters| letters.chars().map(|char| (char, None)))
+    };
+
+    let mut letters: Vec<(char, Option<usize>)> = model
+        .text
+        .chars()
+        .enumerate()
+        .flat_map(|(index, char)| extra_letters_at(index).chain([(char, Some(index))]))
+        .collect();
+    letters.extend(extra_letters_at(model.text_length));
+    letters
+}
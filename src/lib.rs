@@ -1,8 +1,14 @@
+use clap::Parser;
 use color_eyre::Result;
 
+use cli::Cli;
+
+mod cli;
+mod config;
 mod features;
+mod storage;
 mod tui;
 
 pub fn run() -> Result<()> {
-    tui::tui::run_tui()
+    Cli::parse().run()
 }
@@ -0,0 +1,122 @@
+use color_eyre::{eyre::WrapErr, Result};
+use rusqlite::{Connection, OptionalExtension};
+
+use crate::features::adaptive::adaptive_progress::AdaptiveProgress;
+
+use super::database::Database;
+
+impl Database {
+    // how far adaptive practice has got, worked out from the adaptive results saved before the
+    // progress was the first time it's needed
+    pub fn load_adaptive_progress(&self) -> Result<AdaptiveProgress> {
+        let progress: Option<String> = self
+            .connection
+            .query_row("SELECT progress FROM adaptive_progress WHERE id = 1", [], |row| {
+                row.get(0)
+            })
+            .optional()
+            .wrap_err("failed to load adaptive progress")?;
+
+        match progress {
+            Some(progress) => {
+                serde_json::from_str(&progress).wrap_err("failed to read adaptive progress")
+            }
+            None => {
+                let progress =
+                    AdaptiveProgress::from_keystroke_logs(&self.load_adaptive_keystrokes()?);
+                save_adaptive_progress(&self.connection, &progress)?;
+                Ok(progress)
+            }
+        }
+    }
+}
+
+pub(super) fn save_adaptive_progress(
+    connection: &Connection,
+    progress: &AdaptiveProgress,
+) -> Result<()> {
+    connection
+        .execute(
+            "INSERT OR REPLACE INTO adaptive_progress (id, progress) VALUES (1, ?1)",
+            [serde_json::to_string(progress)?],
+        )
+        .wrap_err("failed to save adaptive progress")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chrono::Local;
+
+    use crate::features::words_challenge::{
+        challenge_mode::ChallengeMode,
+        challenge_result::ChallengeResult,
+        challenge_settings::ChallengeSettings,
+        keystroke::{Keystroke, KeystrokeKey},
+        stop_on_error::StopOnError,
+        word_generator::WordBand,
+    };
+
+    use super::*;
+
+    fn adaptive_result(text: &str) -> ChallengeResult {
+        let keystrokes = text
+            .chars()
+            .enumerate()
+            .map(|(index, char)| Keystroke {
+                key: KeystrokeKey::Character(char),
+                expected: Some(char),
+                position: index,
+                time: Duration::from_millis(index as u64 * 150),
+            })
+            .collect();
+
+        ChallengeResult {
+            settings: ChallengeSettings::new(ChallengeMode::Adaptive(1), WordBand::Top200),
+            seed: 0,
+            wpm: 80.0,
+            accuracy: 100.0,
+            duration: Duration::from_secs(10),
+            correct_characters: text.len(),
+            incorrect_characters: 0,
+            completed_at: Local::now(),
+            wpm_samples: Vec::new(),
+            keystrokes,
+            quote_id: None,
+            custom_text: Some(text.to_string()),
+            stop_on_error: StopOnError::Off,
+            failure: None,
+            mistyped_words: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn progress_is_saved_with_adaptive_results_and_kept_after_deleting_them() {
+        let database = Database::open_in_memory().unwrap();
+        let result = adaptive_result(&"entirl".repeat(20));
+        let expected = AdaptiveProgress::default().with_keystrokes(&result.keystrokes);
+
+        let id = database.save_result(&result).unwrap();
+        assert_eq!(database.load_adaptive_progress().unwrap(), expected);
+        assert_eq!(expected.unlocked().len(), 7);
+
+        database.delete_result(id).unwrap();
+        assert_eq!(database.load_adaptive_progress().unwrap(), expected);
+    }
+
+    #[test]
+    fn progress_is_worked_out_from_results_saved_before_it_was() {
+        let database = Database::open_in_memory().unwrap();
+        let result = adaptive_result(&"entirl".repeat(20));
+        database.save_result(&result).unwrap();
+        database.connection.execute("DELETE FROM adaptive_progress", []).unwrap();
+
+        assert_eq!(
+            database.load_adaptive_progress().unwrap(),
+            AdaptiveProgress::from_keystroke_logs(&[result.keystrokes])
+        );
+    }
+}
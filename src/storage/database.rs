@@ -0,0 +1,78 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::{
+    eyre::{eyre, WrapErr},
+    Result,
+};
+use rusqlite::Connection;
+
+use super::migrations;
+
+const DATABASE_FILE_NAME: &str = "typee.db";
+
+// The local database that finished challenges are saved to
+pub struct Database {
+    pub(super) connection: Connection,
+}
+
+impl Database {
+    // opens the database in the given directory, or the user's data directory when there isn't
+    // one, e.g. ~/.local/share/typee on linux, creating it if this is the first run
+    pub fn open(data_directory: Option<&Path>) -> Result<Self> {
+        let data_directory = match data_directory {
+            Some(data_directory) => data_directory.to_path_buf(),
+            None => default_data_directory()?,
+        };
+        fs::create_dir_all(&data_directory).wrap_err_with(|| {
+            format!("failed to create data directory {}", data_directory.display())
+        })?;
+
+        let path = data_directory.join(DATABASE_FILE_NAME);
+        let connection = Connection::open(&path)
+            .wrap_err_with(|| format!("failed to open database {}", path.display()))?;
+        connection.pragma_update(None, "journal_mode", "WAL")?;
+
+        Self::from_connection(connection)
+    }
+
+    #[cfg(test)]
+    pub fn open_in_memory() -> Result<Self> {
+        Self::from_connection(Connection::open_in_memory()?)
+    }
+
+    fn from_connection(mut connection: Connection) -> Result<Self> {
+        migrations::migrate(&mut connection)?;
+        Ok(Self { connection })
+    }
+}
+
+fn default_data_directory() -> Result<PathBuf> {
+    dirs::data_dir()
+        .map(|directory| directory.join("typee"))
+        .ok_or_else(|| eyre!("could not find a data directory to store results in"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_database_is_migrated_to_latest_version() {
+        let database = Database::open_in_memory().unwrap();
+        let version: usize = database
+            .connection
+            .pragma_query_value(None, "user_version", |row| row.get(0))
+            .unwrap();
+
+        assert_eq!(version, migrations::latest_version());
+    }
+
+    #[test]
+    fn migrating_twice_does_nothing() {
+        let mut database = Database::open_in_memory().unwrap();
+        migrations::migrate(&mut database.connection).unwrap();
+    }
+}
@@ -0,0 +1,39 @@
+use color_eyre::{eyre::WrapErr, Result};
+use rusqlite::Connection;
+
+// Every schema change in the order it was made. A database records how many of these it has had
+// applied in its user_version, so new changes must only ever be added to the end
+const MIGRATIONS: &[&str] = &[
+    include_str!("migrations/001_create_results.sql"),
+    include_str!("migrations/002_add_personal_best_columns.sql"),
+    include_str!("migrations/003_add_quote_id.sql"),
+    include_str!("migrations/004_add_custom_text.sql"),
+    include_str!("migrations/005_add_stop_on_error.sql"),
+    include_str!("migrations/006_add_failure.sql"),
+    include_str!("migrations/007_add_mistyped_words.sql"),
+    include_str!("migrations/008_create_key_stats.sql"),
+    include_str!("migrations/009_create_adaptive_progress.sql"),
+];
+
+#[cfg(test)]
+pub fn latest_version() -> usize {
+    MIGRATIONS.len()
+}
+
+// applies any migrations the database hasn't had yet, each in its own transaction
+pub fn migrate(connection: &mut Connection) -> Result<()> {
+    let current_version: usize =
+        connection.pragma_query_value(None, "user_version", |row| row.get(0))?;
+
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(current_version) {
+        let version = index + 1;
+        let transaction = connection.transaction()?;
+        transaction
+            .execute_batch(migration)
+            .wrap_err_with(|| format!("failed to apply database migration {}", version))?;
+        transaction.pragma_update(None, "user_version", version)?;
+        transaction.commit()?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,25 @@
+CREATE TABLE results (
+    id INTEGER PRIMARY KEY,
+    -- 'words' or 'time', with the word count or seconds in mode_length
+    mode TEXT NOT NULL,
+    mode_length INTEGER NOT NULL,
+    language TEXT NOT NULL,
+    word_band TEXT NOT NULL,
+    -- the u64 seed stored with the same bits as an i64
+    seed INTEGER NOT NULL,
+    wpm REAL NOT NULL,
+    accuracy REAL NOT NULL,
+    duration_ms INTEGER NOT NULL,
+    correct_characters INTEGER NOT NULL,
+    incorrect_characters INTEGER NOT NULL,
+    -- unix timestamp in seconds
+    completed_at INTEGER NOT NULL,
+    -- json arrays, kept out of list queries so the history stays quick to load
+    wpm_samples TEXT NOT NULL,
+    keystrokes TEXT NOT NULL DEFAULT '[]'
+);
+
+CREATE INDEX results_by_completed_at ON results (completed_at);
+CREATE INDEX results_by_mode ON results (mode, mode_length, language, completed_at);
+CREATE INDEX results_by_wpm ON results (wpm);
+CREATE INDEX results_by_accuracy ON results (accuracy);
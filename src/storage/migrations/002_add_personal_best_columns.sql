@@ -0,0 +1,8 @@
+-- results are only compared with others that had the same punctuation and numbers settings
+ALTER TABLE results ADD COLUMN punctuation INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE results ADD COLUMN numbers INTEGER NOT NULL DEFAULT 0;
+
+-- lets the best wpm for a test be found without scanning its whole history
+CREATE INDEX results_personal_best ON results (
+    mode, mode_length, language, word_band, punctuation, numbers, wpm
+);
@@ -0,0 +1,5 @@
+-- the quote typed in quote mode, so results for the same quote can be compared. The mode_length
+-- of a quote result is its length bucket, 0 for short up to 3 for thicc
+ALTER TABLE results ADD COLUMN quote_id INTEGER;
+
+CREATE INDEX results_by_quote ON results (quote_id) WHERE quote_id IS NOT NULL;
@@ -0,0 +1,3 @@
+-- the text typed in custom mode, so the result can be retried and replayed without the file it
+-- came from. The mode_length of a custom result is its word count
+ALTER TABLE results ADD COLUMN custom_text TEXT;
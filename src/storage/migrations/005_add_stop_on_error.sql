@@ -0,0 +1,3 @@
+-- whether the caret stopped on mistakes, so a replay follows the same rules as the run did.
+-- Results from before it existed were all typed with it off
+ALTER TABLE results ADD COLUMN stop_on_error TEXT NOT NULL DEFAULT 'off';
@@ -0,0 +1,3 @@
+-- why a test was failed before it finished as json, or nothing for a test that wasn't failed.
+-- Failed results are left out of personal bests
+ALTER TABLE results ADD COLUMN failure TEXT;
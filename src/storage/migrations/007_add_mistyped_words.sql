@@ -0,0 +1,3 @@
+-- the words that had a mistake in them as a json list, for practising them again. Results from
+-- before it existed have none
+ALTER TABLE results ADD COLUMN mistyped_words TEXT NOT NULL DEFAULT '[]';
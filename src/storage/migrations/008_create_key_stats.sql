@@ -0,0 +1,19 @@
+-- how each character was typed in each result, so key stats can be added up without going
+-- through every keystroke ever saved. The latencies are the latest few correct presses' times in
+-- microseconds as a json list, for the p90
+CREATE TABLE key_stats (
+    result_id INTEGER NOT NULL,
+    character TEXT NOT NULL,
+    presses INTEGER NOT NULL,
+    errors INTEGER NOT NULL,
+    latency_total_us INTEGER NOT NULL,
+    latency_count INTEGER NOT NULL,
+    latencies TEXT NOT NULL,
+    PRIMARY KEY (result_id, character)
+);
+
+CREATE INDEX key_stats_by_character ON key_stats (character, result_id);
+
+-- results saved before this have theirs worked out from their keystrokes the first time the key
+-- stats are loaded
+ALTER TABLE results ADD COLUMN key_stats_saved INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,7 @@
+-- how far adaptive practice has got as json, saved with every adaptive result so deleting
+-- results never takes it back. There's only ever the one row, which is first worked out from any
+-- adaptive results saved before it existed
+CREATE TABLE adaptive_progress (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    progress TEXT NOT NULL
+);
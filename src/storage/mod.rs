@@ -0,0 +1,4 @@
+pub(crate) mod adaptive_progress_table;
+pub(crate) mod database;
+mod migrations;
+pub(crate) mod results_table;
@@ -0,0 +1,1111 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use chrono::{DateTime, Local, TimeZone};
+use color_eyre::{
+    eyre::{eyre, WrapErr},
+    Result,
+};
+use rusqlite::{
+    params, params_from_iter, types::Type, types::Value, Connection, OptionalExtension, Row,
+};
+use serde::de::DeserializeOwned;
+
+use crate::features::{
+    history::{
+        history_query::{HistoryQuery, HistorySort, ModeFilter},
+        result_summary::ResultSummary,
+    },
+    stats::key_stats::{KeyRecord, KeyStats, RECENT_LATENCIES},
+    words_challenge::{
+        challenge_mode::ChallengeMode, challenge_result::ChallengeResult,
+        challenge_settings::ChallengeSettings, keystroke::Keystroke, quote::QuoteLength,
+        stop_on_error::StopOnError, word_generator::WordBand,
+    },
+};
+
+use super::{adaptive_progress_table::save_adaptive_progress, database::Database};
+
+// only english word lists exist so far
+const LANGUAGE: &str = "english";
+// none of the word lists have punctuation or numbers yet, the columns are kept so that results
+// with them are never compared against results without
+const PUNCTUATION: bool = false;
+const NUMBERS: bool = false;
+
+// matches the results for the same test, only these are ever compared with each other
+const SAME_TEST_CONDITION: &str = "mode = ? AND mode_length = ? AND language = ?
+    AND word_band = ? AND punctuation = ? AND numbers = ?";
+// quotes are picked without using the word band, so any quote of the same length is the same test
+const SAME_QUOTE_LENGTH_CONDITION: &str = "mode = ? AND mode_length = ? AND language = ?
+    AND punctuation = ? AND numbers = ?";
+// custom text and adaptive lessons of the same length can be completely different texts, they're
+// only the same test when they're the same text
+const SAME_TEXT_CONDITION: &str = "mode = ? AND custom_text = ?";
+
+impl Database {
+    // saves a finished challenge, returning the id of its new row
+    pub fn save_result(&self, result: &ChallengeResult) -> Result<i64> {
+        let (mode, mode_length) = mode_columns(result.settings.mode);
+        let wpm_samples = serde_json::to_string(&result.wpm_samples)?;
+        let keystrokes = serde_json::to_string(&result.keystrokes)?;
+        let failure = result.failure.map(|failure| serde_json::to_string(&failure)).transpose()?;
+        let mistyped_words = serde_json::to_string(&result.mistyped_words)?;
+        // adaptive practice carries on from where it had got to before this result
+        let adaptive_progress = match result.settings.mode {
+            ChallengeMode::Adaptive(_) => {
+                Some(self.load_adaptive_progress()?.with_keystrokes(&result.keystrokes))
+            }
+            _ => None,
+        };
+
+        let transaction = self.connection.unchecked_transaction()?;
+        transaction
+            .execute(
+                "INSERT INTO results (
+                    mode, mode_length, language, word_band, seed, wpm, accuracy, duration_ms,
+                    correct_characters, incorrect_characters, completed_at, wpm_samples,
+                    punctuation, numbers, keystrokes, quote_id, custom_text, stop_on_error, failure,
+                    mistyped_words
+                ) VALUES (
+                    ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18,
+                    ?19, ?20
+                )",
+                params![
+                    mode,
+                    mode_length,
+                    LANGUAGE,
+                    word_band_column(result.settings.word_band),
+                    // sqlite integers are signed, the seed keeps its bits and is cast back on load
+                    result.seed as i64,
+                    result.wpm,
+                    result.accuracy,
+                    result.duration.as_millis() as i64,
+                    result.correct_characters as i64,
+                    result.incorrect_characters as i64,
+                    result.completed_at.timestamp(),
+                    wpm_samples,
+                    PUNCTUATION,
+                    NUMBERS,
+                    keystrokes,
+                    result.quote_id,
+                    result.custom_text,
+                    stop_on_error_column(result.stop_on_error),
+                    failure,
+                    mistyped_words,
+                ],
+            )
+            .wrap_err("failed to save challenge result")?;
+        let id = transaction.last_insert_rowid();
+
+        save_key_stats(&transaction, id, &result.keystrokes)?;
+        if let Some(adaptive_progress) = adaptive_progress {
+            save_adaptive_progress(&transaction, &adaptive_progress)?;
+        }
+        transaction.commit()?;
+
+        Ok(id)
+    }
+
+    pub fn load_result(&self, id: i64) -> Result<ChallengeResult> {
+        self.connection
+            .query_row(
+                "SELECT mode, mode_length, word_band, seed, wpm, accuracy, duration_ms,
+                    correct_characters, incorrect_characters, completed_at, wpm_samples, keystrokes,
+                    quote_id, custom_text, stop_on_error, failure, mistyped_words
+                FROM results WHERE id = ?1",
+                [id],
+                |row| {
+                    Ok(ChallengeResult {
+                        settings: settings_from_row(row, 0, 1, 2)?,
+                        seed: row.get::<_, i64>(3)? as u64,
+                        wpm: row.get(4)?,
+                        accuracy: row.get(5)?,
+                        duration: Duration::from_millis(row.get(6)?),
+                        correct_characters: row.get(7)?,
+                        incorrect_characters: row.get(8)?,
+                        completed_at: timestamp_from_row(row, 9)?,
+                        wpm_samples: json_from_row(row, 10)?,
+                        keystrokes: json_from_row(row, 11)?,
+                        quote_id: row.get(12)?,
+                        custom_text: row.get(13)?,
+                        stop_on_error: stop_on_error_from_row(row, 14)?,
+                        failure: optional_json_from_row(row, 15)?,
+                        mistyped_words: json_from_row(row, 16)?,
+                    })
+                },
+            )
+            .wrap_err_with(|| format!("failed to load result {}", id))
+    }
+
+    // lists the results matching the query, leaving out the samples so that even a long history
+    // loads quickly
+    pub fn load_result_summaries(&self, query: &HistoryQuery) -> Result<Vec<ResultSummary>> {
+        let mut conditions = Vec::new();
+        let mut values: Vec<Value> = Vec::new();
+
+        let (mode, mode_length) = match query.mode {
+            ModeFilter::All => (None, None),
+            ModeFilter::Words(num_words) => (Some("words"), num_words.map(|num| num as i64)),
+            ModeFilter::Time(seconds) => (Some("time"), seconds.map(|secs| secs as i64)),
+            ModeFilter::Quote(length) => (Some("quote"), length.map(quote_length_column)),
+            ModeFilter::Custom => (Some("custom"), None),
+            ModeFilter::Adaptive => (Some("adaptive"), None),
+        };
+        if let Some(mode) = mode {
+            conditions.push("mode = ?");
+            values.push(Value::Text(mode.to_string()));
+        }
+        if let Some(mode_length) = mode_length {
+            conditions.push("mode_length = ?");
+            values.push(Value::Integer(mode_length));
+        }
+        if let Some(language) = &query.language {
+            conditions.push("language = ?");
+            values.push(Value::Text(language.clone()));
+        }
+
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
+        let order_column = match query.sort {
+            HistorySort::Date => "completed_at",
+            HistorySort::Wpm => "wpm",
+            HistorySort::Accuracy => "accuracy",
+        };
+
+        // sqlite treats a negative limit as no limit at all
+        values.push(Value::Integer(query.limit.map_or(-1, |limit| limit as i64)));
+
+        let sql = format!(
+            "SELECT id, mode, mode_length, word_band, language, wpm, accuracy, duration_ms,
+                completed_at, failure IS NOT NULL
+            FROM results {} ORDER BY {} DESC, id DESC LIMIT ?",
+            where_clause, order_column
+        );
+
+        let mut statement = self.connection.prepare(&sql)?;
+        let summaries = statement
+            .query_map(params_from_iter(values), |row| {
+                Ok(ResultSummary {
+                    id: row.get(0)?,
+                    settings: settings_from_row(row, 1, 2, 3)?,
+                    language: row.get(4)?,
+                    wpm: row.get(5)?,
+                    accuracy: row.get(6)?,
+                    duration: Duration::from_millis(row.get(7)?),
+                    completed_at: timestamp_from_row(row, 8)?,
+                    failed: row.get(9)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .wrap_err("failed to load result history")?;
+
+        Ok(summaries)
+    }
+
+    pub fn delete_result(&self, id: i64) -> Result<()> {
+        let transaction = self.connection.unchecked_transaction()?;
+        transaction
+            .execute("DELETE FROM key_stats WHERE result_id = ?1", [id])
+            .and_then(|_| transaction.execute("DELETE FROM results WHERE id = ?1", [id]))
+            .wrap_err_with(|| format!("failed to delete result {}", id))?;
+        transaction.commit()?;
+
+        Ok(())
+    }
+
+    // The best wpm of any result for the same test saved before the given one, failed results
+    // are never a best. Bests are always worked out from the results that still exist rather than
+    // stored, so deleting a result can never leave a stale best behind
+    pub fn personal_best_before(&self, result: &ChallengeResult, id: i64) -> Result<Option<f32>> {
+        let Some((condition, mut values)) =
+            same_test_filter(&result.settings, result.custom_text.as_deref())
+        else {
+            return Ok(None);
+        };
+        values.push(Value::Integer(id));
+
+        self.connection
+            .query_row(
+                &format!(
+                    "SELECT MAX(wpm) FROM results WHERE {} AND id < ? AND failure IS NULL",
+                    condition
+                ),
+                params_from_iter(values),
+                |row| row.get(0),
+            )
+            .wrap_err("failed to load personal best")
+    }
+
+    // The fastest result for the same test that wasn't failed and has keystrokes to follow. A new
+    // custom text or adaptive lesson has never been done before so it never has one
+    pub fn personal_best_id(&self, settings: &ChallengeSettings) -> Result<Option<i64>> {
+        let Some((condition, values)) = same_test_filter(settings, None) else {
+            return Ok(None);
+        };
+
+        self.connection
+            .query_row(
+                &format!(
+                    "SELECT id FROM results WHERE {} AND keystrokes != '[]' AND failure IS NULL
+                    ORDER BY wpm DESC, id DESC LIMIT 1",
+                    condition
+                ),
+                params_from_iter(values),
+                |row| row.get(0),
+            )
+            .optional()
+            .wrap_err("failed to find personal best")
+    }
+
+    // the most recent result for the same test that has keystrokes to follow
+    pub fn last_result_id(&self, settings: &ChallengeSettings) -> Result<Option<i64>> {
+        let Some((condition, values)) = same_test_filter(settings, None) else {
+            return Ok(None);
+        };
+
+        self.connection
+            .query_row(
+                &format!(
+                    "SELECT id FROM results WHERE {} AND keystrokes != '[]'
+                    ORDER BY id DESC LIMIT 1",
+                    condition
+                ),
+                params_from_iter(values),
+                |row| row.get(0),
+            )
+            .optional()
+            .wrap_err("failed to find last result")
+    }
+
+    // every language that has a saved result, for filtering the history by
+    pub fn load_languages(&self) -> Result<Vec<String>> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT DISTINCT language FROM results ORDER BY language")?;
+        let languages = statement
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(languages)
+    }
+
+    // The key stats added up across every saved result. Only the latest presses of each
+    // character are loaded for its p90, from as many of the latest results as could have them
+    pub fn load_key_stats(&self) -> Result<KeyStats> {
+        self.save_missing_key_stats()?;
+
+        let mut statement = self.connection.prepare(
+            "SELECT character, SUM(presses), SUM(errors), SUM(latency_total_us), SUM(latency_count)
+            FROM key_stats GROUP BY character",
+        )?;
+        let mut records = statement
+            .query_map([], |row| {
+                Ok((
+                    character_from_row(row, 0)?,
+                    KeyRecord::new(
+                        row.get(1)?,
+                        row.get(2)?,
+                        Duration::from_micros(row.get(3)?),
+                        row.get(4)?,
+                    ),
+                ))
+            })?
+            .collect::<rusqlite::Result<BTreeMap<_, _>>>()
+            .wrap_err("failed to load key stats")?;
+
+        let mut statement = self.connection.prepare(
+            "SELECT character, latencies FROM (
+                SELECT result_id, character, latencies, ROW_NUMBER() OVER (
+                    PARTITION BY character ORDER BY result_id DESC
+                ) AS recency
+                FROM key_stats WHERE latency_count > 0
+            )
+            WHERE recency <= ?1 ORDER BY result_id",
+        )?;
+        let recent_latencies = statement
+            .query_map([RECENT_LATENCIES as i64], |row| {
+                let latencies: Vec<u64> = json_from_row(row, 1)?;
+                Ok((character_from_row(row, 0)?, latencies))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .wrap_err("failed to load key latencies")?;
+        for (character, latencies) in recent_latencies {
+            if let Some(record) = records.remove(&character) {
+                let latencies = latencies.into_iter().map(Duration::from_micros);
+                records.insert(character, record.with_recent_latencies(latencies));
+            }
+        }
+
+        Ok(KeyStats::from_records(records))
+    }
+
+    // works out the key stats of results saved before they were stored with each result, which
+    // only ever has to be done once
+    fn save_missing_key_stats(&self) -> Result<()> {
+        let transaction = self.connection.unchecked_transaction()?;
+        let unsaved = transaction
+            .prepare("SELECT id, keystrokes FROM results WHERE key_stats_saved = 0")?
+            .query_map([], |row| Ok((row.get(0)?, json_from_row::<Vec<Keystroke>>(row, 1)?)))?
+            .collect::<rusqlite::Result<Vec<(i64, _)>>>()?;
+
+        for (id, keystrokes) in unsaved {
+            save_key_stats(&transaction, id, &keystrokes)?;
+        }
+        transaction.commit()?;
+
+        Ok(())
+    }
+
+    // the keystrokes of every adaptive result, oldest first, which is all adaptive practice needs
+    // to work out how far it got before its progress was saved
+    pub fn load_adaptive_keystrokes(&self) -> Result<Vec<Vec<Keystroke>>> {
+        let mut statement = self.connection.prepare(
+            "SELECT keystrokes FROM results WHERE mode = 'adaptive' AND keystrokes != '[]'
+            ORDER BY id",
+        )?;
+        let keystrokes = statement
+            .query_map([], |row| json_from_row(row, 0))?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .wrap_err("failed to load adaptive progress")?;
+
+        Ok(keystrokes)
+    }
+}
+
+// stores how each character was typed in a result, alongside the result itself
+fn save_key_stats(connection: &Connection, result_id: i64, keystrokes: &[Keystroke]) -> Result<()> {
+    let mut statement = connection.prepare(
+        "INSERT INTO key_stats (
+            result_id, character, presses, errors, latency_total_us, latency_count, latencies
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+    )?;
+    for (character, record) in KeyStats::from_keystrokes(keystrokes).records() {
+        let latencies: Vec<u64> = record
+            .recent_latencies()
+            .map(|latency| latency.as_micros() as u64)
+            .collect();
+        statement
+            .execute(params![
+                result_id,
+                character.to_string(),
+                record.presses as i64,
+                record.errors as i64,
+                record.latency_total.as_micros() as i64,
+                record.latency_count as i64,
+                serde_json::to_string(&latencies)?,
+            ])
+            .wrap_err("failed to save key stats")?;
+    }
+
+    connection
+        .execute("UPDATE results SET key_stats_saved = 1 WHERE id = ?1", [result_id])
+        .wrap_err("failed to save key stats")?;
+
+    Ok(())
+}
+
+fn mode_columns(mode: ChallengeMode) -> (&'static str, i64) {
+    match mode {
+        ChallengeMode::Words(num_words) => ("words", num_words as i64),
+        ChallengeMode::Time(seconds) => ("time", seconds as i64),
+        ChallengeMode::Quote(length) => ("quote", quote_length_column(length)),
+        ChallengeMode::Custom(num_words) => ("custom", num_words as i64),
+        ChallengeMode::Adaptive(num_words) => ("adaptive", num_words as i64),
+    }
+}
+
+fn quote_length_column(length: QuoteLength) -> i64 {
+    match length {
+        QuoteLength::Short => 0,
+        QuoteLength::Medium => 1,
+        QuoteLength::Long => 2,
+        QuoteLength::Thicc => 3,
+    }
+}
+
+fn word_band_column(word_band: WordBand) -> &'static str {
+    match word_band {
+        WordBand::Top200 => "200",
+        WordBand::Top1k => "1k",
+        WordBand::Top10k => "10k",
+    }
+}
+
+fn stop_on_error_column(stop_on_error: StopOnError) -> &'static str {
+    match stop_on_error {
+        StopOnError::Off => "off",
+        StopOnError::Letter => "letter",
+        StopOnError::Word => "word",
+    }
+}
+
+// The condition and its values matching results for the same test as the given settings, with
+// the text for custom text and adaptive lessons. Without the text there's nothing to match
+fn same_test_filter(
+    settings: &ChallengeSettings,
+    custom_text: Option<&str>,
+) -> Option<(&'static str, Vec<Value>)> {
+    let (mode, mode_length) = mode_columns(settings.mode);
+
+    let filter = match settings.mode {
+        ChallengeMode::Words(_) | ChallengeMode::Time(_) => (
+            SAME_TEST_CONDITION,
+            vec![
+                Value::Text(mode.to_string()),
+                Value::Integer(mode_length),
+                Value::Text(LANGUAGE.to_string()),
+                Value::Text(word_band_column(settings.word_band).to_string()),
+                Value::Integer(PUNCTUATION as i64),
+                Value::Integer(NUMBERS as i64),
+            ],
+        ),
+        ChallengeMode::Quote(_) => (
+            SAME_QUOTE_LENGTH_CONDITION,
+            vec![
+                Value::Text(mode.to_string()),
+                Value::Integer(mode_length),
+                Value::Text(LANGUAGE.to_string()),
+                Value::Integer(PUNCTUATION as i64),
+                Value::Integer(NUMBERS as i64),
+            ],
+        ),
+        ChallengeMode::Custom(_) | ChallengeMode::Adaptive(_) => (
+            SAME_TEXT_CONDITION,
+            vec![
+                Value::Text(mode.to_string()),
+                Value::Text(custom_text?.to_string()),
+            ],
+        ),
+    };
+
+    Some(filter)
+}
+
+fn settings_from_columns(mode: &str, mode_length: i64, word_band: &str) -> Result<ChallengeSettings> {
+    let mode = match mode {
+        "words" => ChallengeMode::Words(mode_length as usize),
+        "time" => ChallengeMode::Time(mode_length as u64),
+        "quote" => match QuoteLength::ALL.get(mode_length as usize) {
+            Some(length) => ChallengeMode::Quote(*length),
+            None => return Err(eyre!("unknown quote length {}", mode_length)),
+        },
+        "custom" => ChallengeMode::Custom(mode_length as usize),
+        "adaptive" => ChallengeMode::Adaptive(mode_length as usize),
+        _ => return Err(eyre!("unknown challenge mode '{}'", mode)),
+    };
+    let word_band = match word_band {
+        "200" => WordBand::Top200,
+        "1k" => WordBand::Top1k,
+        "10k" => WordBand::Top10k,
+        _ => return Err(eyre!("unknown word band '{}'", word_band)),
+    };
+
+    Ok(ChallengeSettings::new(mode, word_band))
+}
+
+// reads the settings from the given mode, mode length and word band columns
+fn settings_from_row(
+    row: &Row,
+    mode_index: usize,
+    mode_length_index: usize,
+    word_band_index: usize,
+) -> rusqlite::Result<ChallengeSettings> {
+    let mode: String = row.get(mode_index)?;
+    let word_band: String = row.get(word_band_index)?;
+
+    settings_from_columns(&mode, row.get(mode_length_index)?, &word_band).map_err(|error| {
+        rusqlite::Error::FromSqlConversionFailure(mode_index, Type::Text, error.into())
+    })
+}
+
+fn stop_on_error_from_row(row: &Row, index: usize) -> rusqlite::Result<StopOnError> {
+    let stop_on_error: String = row.get(index)?;
+
+    match stop_on_error.as_str() {
+        "off" => Ok(StopOnError::Off),
+        "letter" => Ok(StopOnError::Letter),
+        "word" => Ok(StopOnError::Word),
+        _ => Err(rusqlite::Error::FromSqlConversionFailure(
+            index,
+            Type::Text,
+            eyre!("unknown stop on error '{}'", stop_on_error).into(),
+        )),
+    }
+}
+
+fn character_from_row(row: &Row, index: usize) -> rusqlite::Result<char> {
+    let character: String = row.get(index)?;
+
+    character.chars().next().ok_or_else(|| {
+        rusqlite::Error::FromSqlConversionFailure(
+            index,
+            Type::Text,
+            eyre!("empty key stats character").into(),
+        )
+    })
+}
+
+fn json_from_row<T: DeserializeOwned>(row: &Row, index: usize) -> rusqlite::Result<T> {
+    let json: String = row.get(index)?;
+
+    serde_json::from_str(&json).map_err(|error| {
+        rusqlite::Error::FromSqlConversionFailure(index, Type::Text, error.into())
+    })
+}
+
+// reads a column that's either json or null
+fn optional_json_from_row<T: DeserializeOwned>(row: &Row, index: usize) -> rusqlite::Result<Option<T>> {
+    let json: Option<String> = row.get(index)?;
+
+    json.map(|json| {
+        serde_json::from_str(&json).map_err(|error| {
+            rusqlite::Error::FromSqlConversionFailure(index, Type::Text, error.into())
+        })
+    })
+    .transpose()
+}
+
+fn timestamp_from_row(row: &Row, index: usize) -> rusqlite::Result<DateTime<Local>> {
+    let timestamp = row.get(index)?;
+
+    Local.timestamp_opt(timestamp, 0).single().ok_or_else(|| {
+        rusqlite::Error::FromSqlConversionFailure(
+            index,
+            Type::Integer,
+            eyre!("invalid timestamp {}", timestamp).into(),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::features::words_challenge::{
+        challenge_result::WpmSample,
+        challenge_settings::ChallengeSettings,
+        fail_conditions::FailReason,
+        keystroke::{Keystroke, KeystrokeKey},
+    };
+
+    use super::*;
+
+    fn result() -> ChallengeResult {
+        result_with(ChallengeMode::Time(30), 72.5, 1_700_000_000)
+    }
+
+    fn result_with(mode: ChallengeMode, wpm: f32, completed_at: i64) -> ChallengeResult {
+        ChallengeResult {
+            settings: ChallengeSettings::new(mode, WordBand::Top1k),
+            seed: u64::MAX,
+            wpm,
+            accuracy: 96.0,
+            duration: Duration::from_millis(30_000),
+            correct_characters: 180,
+            incorrect_characters: 7,
+            completed_at: Local.timestamp_opt(completed_at, 0).unwrap(),
+            wpm_samples: vec![WpmSample {
+                wpm: 60.0,
+                raw_wpm: 72.0,
+                errors: 1,
+            }],
+            keystrokes: vec![Keystroke {
+                key: KeystrokeKey::Character('x'),
+                expected: Some('t'),
+                position: 0,
+                time: Duration::ZERO,
+            }],
+            quote_id: None,
+            custom_text: None,
+            stop_on_error: StopOnError::Off,
+            failure: None,
+            mistyped_words: vec!["quick".to_string()],
+        }
+    }
+
+    #[test]
+    fn saved_result_is_stored_in_a_row() {
+        let database = Database::open_in_memory().unwrap();
+        let id = database.save_result(&result()).unwrap();
+
+        let row: (String, i64, String, i64, f32, i64, i64) = database
+            .connection
+            .query_row(
+                "SELECT mode, mode_length, word_band, seed, wpm, duration_ms, completed_at
+                 FROM results WHERE id = ?1",
+                [id],
+                |row| {
+                    Ok((
+                        row.get(0)?,
+                        row.get(1)?,
+                        row.get(2)?,
+                        row.get(3)?,
+                        row.get(4)?,
+                        row.get(5)?,
+                        row.get(6)?,
+                    ))
+                },
+            )
+            .unwrap();
+
+        assert_eq!(
+            row,
+            (
+                "time".to_string(),
+                30,
+                "1k".to_string(),
+                u64::MAX as i64,
+                72.5,
+                30_000,
+                1_700_000_000,
+            )
+        );
+    }
+
+    #[test]
+    fn saved_seed_keeps_its_bits() {
+        let database = Database::open_in_memory().unwrap();
+        let id = database.save_result(&result()).unwrap();
+
+        let seed: i64 = database
+            .connection
+            .query_row("SELECT seed FROM results WHERE id = ?1", [id], |row| row.get(0))
+            .unwrap();
+
+        assert_eq!(seed as u64, u64::MAX);
+    }
+
+    #[test]
+    fn wpm_samples_are_stored_as_json() {
+        let database = Database::open_in_memory().unwrap();
+        let id = database.save_result(&result()).unwrap();
+
+        let wpm_samples: String = database
+            .connection
+            .query_row("SELECT wpm_samples FROM results WHERE id = ?1", [id], |row| row.get(0))
+            .unwrap();
+        let wpm_samples: Vec<WpmSample> = serde_json::from_str(&wpm_samples).unwrap();
+
+        assert_eq!(wpm_samples, result().wpm_samples);
+    }
+
+    #[test]
+    fn loaded_result_matches_saved_result() {
+        let database = Database::open_in_memory().unwrap();
+        let id = database.save_result(&result()).unwrap();
+
+        assert_eq!(database.load_result(id).unwrap(), result());
+    }
+
+    #[test]
+    fn loading_missing_result_is_an_error() {
+        let database = Database::open_in_memory().unwrap();
+        assert!(database.load_result(1).is_err());
+    }
+
+    #[test]
+    fn summaries_are_newest_first_by_default() {
+        let database = Database::open_in_memory().unwrap();
+        let older = database
+            .save_result(&result_with(ChallengeMode::Words(25), 90.0, 1_000))
+            .unwrap();
+        let newer = database
+            .save_result(&result_with(ChallengeMode::Words(25), 50.0, 2_000))
+            .unwrap();
+
+        let summaries = database.load_result_summaries(&HistoryQuery::default()).unwrap();
+        let ids: Vec<i64> = summaries.iter().map(|summary| summary.id).collect();
+
+        assert_eq!(ids, vec![newer, older]);
+    }
+
+    #[test]
+    fn summaries_can_be_sorted_by_wpm() {
+        let database = Database::open_in_memory().unwrap();
+        let fast = database
+            .save_result(&result_with(ChallengeMode::Words(25), 90.0, 1_000))
+            .unwrap();
+        let slow = database
+            .save_result(&result_with(ChallengeMode::Words(25), 50.0, 2_000))
+            .unwrap();
+
+        let query = HistoryQuery {
+            sort: HistorySort::Wpm,
+            ..Default::default()
+        };
+        let summaries = database.load_result_summaries(&query).unwrap();
+        let ids: Vec<i64> = summaries.iter().map(|summary| summary.id).collect();
+
+        assert_eq!(ids, vec![fast, slow]);
+    }
+
+    #[test]
+    fn summaries_can_be_limited_after_sorting() {
+        let database = Database::open_in_memory().unwrap();
+        let fast = database
+            .save_result(&result_with(ChallengeMode::Words(25), 90.0, 1_000))
+            .unwrap();
+        database
+            .save_result(&result_with(ChallengeMode::Words(25), 50.0, 2_000))
+            .unwrap();
+        let faster = database
+            .save_result(&result_with(ChallengeMode::Words(25), 95.0, 3_000))
+            .unwrap();
+
+        let query = HistoryQuery {
+            sort: HistorySort::Wpm,
+            limit: Some(2),
+            ..Default::default()
+        };
+        let summaries = database.load_result_summaries(&query).unwrap();
+        let ids: Vec<i64> = summaries.iter().map(|summary| summary.id).collect();
+
+        assert_eq!(ids, vec![faster, fast]);
+    }
+
+    #[test]
+    fn summaries_can_be_filtered_by_mode_and_length() {
+        let database = Database::open_in_memory().unwrap();
+        database
+            .save_result(&result_with(ChallengeMode::Words(25), 90.0, 1_000))
+            .unwrap();
+        let words_50 = database
+            .save_result(&result_with(ChallengeMode::Words(50), 80.0, 2_000))
+            .unwrap();
+        let time_30 = database
+            .save_result(&result_with(ChallengeMode::Time(30), 70.0, 3_000))
+            .unwrap();
+
+        let words_query = HistoryQuery {
+            mode: ModeFilter::Words(Some(50)),
+            ..Default::default()
+        };
+        let words_summaries = database.load_result_summaries(&words_query).unwrap();
+        assert_eq!(words_summaries.len(), 1);
+        assert_eq!(words_summaries[0].id, words_50);
+
+        let time_query = HistoryQuery {
+            mode: ModeFilter::Time(None),
+            ..Default::default()
+        };
+        let time_summaries = database.load_result_summaries(&time_query).unwrap();
+        assert_eq!(time_summaries.len(), 1);
+        assert_eq!(time_summaries[0].id, time_30);
+    }
+
+    #[test]
+    fn summaries_can_be_filtered_by_language() {
+        let database = Database::open_in_memory().unwrap();
+        database.save_result(&result()).unwrap();
+
+        let english_query = HistoryQuery {
+            language: Some("english".to_string()),
+            ..Default::default()
+        };
+        let other_query = HistoryQuery {
+            language: Some("german".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(database.load_result_summaries(&english_query).unwrap().len(), 1);
+        assert!(database.load_result_summaries(&other_query).unwrap().is_empty());
+        assert_eq!(database.load_languages().unwrap(), vec!["english".to_string()]);
+    }
+
+    #[test]
+    fn first_result_has_no_previous_best() {
+        let database = Database::open_in_memory().unwrap();
+        let result = result();
+        let id = database.save_result(&result).unwrap();
+
+        assert_eq!(database.personal_best_before(&result, id).unwrap(), None);
+    }
+
+    #[test]
+    fn previous_best_is_fastest_earlier_result_with_same_settings() {
+        let database = Database::open_in_memory().unwrap();
+        database
+            .save_result(&result_with(ChallengeMode::Words(25), 80.0, 1_000))
+            .unwrap();
+        database
+            .save_result(&result_with(ChallengeMode::Words(25), 60.0, 2_000))
+            .unwrap();
+        // different settings are never compared
+        database
+            .save_result(&result_with(ChallengeMode::Words(50), 120.0, 3_000))
+            .unwrap();
+        let latest = result_with(ChallengeMode::Words(25), 70.0, 4_000);
+        let latest_id = database.save_result(&latest).unwrap();
+        // later results don't count towards the best before this one
+        database
+            .save_result(&result_with(ChallengeMode::Words(25), 100.0, 5_000))
+            .unwrap();
+
+        assert_eq!(
+            database.personal_best_before(&latest, latest_id).unwrap(),
+            Some(80.0)
+        );
+    }
+
+    #[test]
+    fn deleting_best_result_falls_back_to_next_best() {
+        let database = Database::open_in_memory().unwrap();
+        let best_id = database
+            .save_result(&result_with(ChallengeMode::Words(25), 80.0, 1_000))
+            .unwrap();
+        database
+            .save_result(&result_with(ChallengeMode::Words(25), 60.0, 2_000))
+            .unwrap();
+        let latest = result_with(ChallengeMode::Words(25), 70.0, 3_000);
+        let latest_id = database.save_result(&latest).unwrap();
+
+        database.delete_result(best_id).unwrap();
+
+        assert_eq!(
+            database.personal_best_before(&latest, latest_id).unwrap(),
+            Some(60.0)
+        );
+    }
+
+    #[test]
+    fn failed_results_are_never_personal_bests() {
+        let database = Database::open_in_memory().unwrap();
+        database
+            .save_result(&result_with(ChallengeMode::Words(25), 60.0, 1_000))
+            .unwrap();
+        let failed_id = database
+            .save_result(&ChallengeResult {
+                failure: Some(FailReason::AccuracyBelow(95.0)),
+                ..result_with(ChallengeMode::Words(25), 90.0, 2_000)
+            })
+            .unwrap();
+        let latest = result_with(ChallengeMode::Words(25), 70.0, 3_000);
+        let latest_id = database.save_result(&latest).unwrap();
+
+        assert_eq!(
+            database.personal_best_before(&latest, latest_id).unwrap(),
+            Some(60.0)
+        );
+        assert_eq!(database.personal_best_id(&latest.settings).unwrap(), Some(latest_id));
+
+        let failed = database.load_result(failed_id).unwrap();
+        assert_eq!(failed.failure, Some(FailReason::AccuracyBelow(95.0)));
+        let summaries = database.load_result_summaries(&HistoryQuery::default()).unwrap();
+        let failed: Vec<bool> = summaries.iter().map(|summary| summary.failed).collect();
+        assert_eq!(failed, vec![false, true, false]);
+    }
+
+    #[test]
+    fn custom_text_is_only_compared_with_the_same_text() {
+        let database = Database::open_in_memory().unwrap();
+        let custom_result = |text: &str, wpm| ChallengeResult {
+            custom_text: Some(text.to_string()),
+            ..result_with(ChallengeMode::Custom(2), wpm, 1_000)
+        };
+        database.save_result(&custom_result("tine lent", 80.0)).unwrap();
+        database.save_result(&custom_result("fox lazy", 90.0)).unwrap();
+        let latest = custom_result("tine lent", 70.0);
+        let latest_id = database.save_result(&latest).unwrap();
+        let other = custom_result("dog cat", 60.0);
+        let other_id = database.save_result(&other).unwrap();
+
+        assert_eq!(database.personal_best_before(&latest, latest_id).unwrap(), Some(80.0));
+        assert_eq!(database.personal_best_before(&other, other_id).unwrap(), None);
+        // a new lesson's text isn't known until it's made so there's nothing to race
+        assert_eq!(database.personal_best_id(&latest.settings).unwrap(), None);
+    }
+
+    #[test]
+    fn quotes_are_compared_whatever_the_word_band() {
+        let database = Database::open_in_memory().unwrap();
+        database
+            .save_result(&ChallengeResult {
+                settings: ChallengeSettings::new(
+                    ChallengeMode::Quote(QuoteLength::Short),
+                    WordBand::Top200,
+                ),
+                quote_id: Some(1),
+                ..result_with(ChallengeMode::Quote(QuoteLength::Short), 80.0, 1_000)
+            })
+            .unwrap();
+        let latest = ChallengeResult {
+            quote_id: Some(2),
+            ..result_with(ChallengeMode::Quote(QuoteLength::Short), 70.0, 2_000)
+        };
+        let latest_id = database.save_result(&latest).unwrap();
+
+        assert_eq!(database.personal_best_before(&latest, latest_id).unwrap(), Some(80.0));
+    }
+
+    #[test]
+    fn personal_best_id_is_fastest_result_with_keystrokes() {
+        let database = Database::open_in_memory().unwrap();
+        let recorded = database
+            .save_result(&result_with(ChallengeMode::Words(25), 70.0, 1_000))
+            .unwrap();
+        database
+            .save_result(&ChallengeResult {
+                keystrokes: Vec::new(),
+                ..result_with(ChallengeMode::Words(25), 90.0, 2_000)
+            })
+            .unwrap();
+        database
+            .save_result(&result_with(ChallengeMode::Words(25), 50.0, 3_000))
+            .unwrap();
+
+        let settings = result_with(ChallengeMode::Words(25), 0.0, 0).settings;
+        assert_eq!(database.personal_best_id(&settings).unwrap(), Some(recorded));
+    }
+
+    #[test]
+    fn last_result_id_is_newest_result_for_same_test() {
+        let database = Database::open_in_memory().unwrap();
+        database
+            .save_result(&result_with(ChallengeMode::Words(25), 70.0, 1_000))
+            .unwrap();
+        let last = database
+            .save_result(&result_with(ChallengeMode::Words(25), 50.0, 2_000))
+            .unwrap();
+        database
+            .save_result(&result_with(ChallengeMode::Time(30), 50.0, 3_000))
+            .unwrap();
+
+        let settings = result_with(ChallengeMode::Words(25), 0.0, 0).settings;
+        assert_eq!(database.last_result_id(&settings).unwrap(), Some(last));
+    }
+
+    #[test]
+    fn no_ghost_result_for_test_never_done() {
+        let database = Database::open_in_memory().unwrap();
+        let settings = result().settings;
+
+        assert_eq!(database.personal_best_id(&settings).unwrap(), None);
+        assert_eq!(database.last_result_id(&settings).unwrap(), None);
+    }
+
+    #[test]
+    fn key_stats_add_up_across_saved_results() {
+        let database = Database::open_in_memory().unwrap();
+        let typed = |keystrokes: &[(char, u64)]| ChallengeResult {
+            keystrokes: keystrokes
+                .iter()
+                .map(|(typed, millis)| Keystroke {
+                    key: KeystrokeKey::Character(*typed),
+                    expected: Some('a'),
+                    position: 0,
+                    time: Duration::from_millis(*millis),
+                })
+                .collect(),
+            ..result()
+        };
+        database.save_result(&typed(&[('a', 0), ('a', 100)])).unwrap();
+        let deleted = database.save_result(&typed(&[('a', 0), ('a', 900)])).unwrap();
+        database.save_result(&typed(&[('x', 0), ('a', 300)])).unwrap();
+        database.delete_result(deleted).unwrap();
+
+        let key_stats = database.load_key_stats().unwrap();
+        let (_, a) = key_stats.records().next().unwrap();
+        assert_eq!((a.presses, a.errors), (4, 1));
+        assert_eq!(a.average_latency(), Some(Duration::from_millis(200)));
+        assert_eq!(
+            a.recent_latencies().collect::<Vec<_>>(),
+            vec![Duration::from_millis(100), Duration::from_millis(300)]
+        );
+    }
+
+    #[test]
+    fn key_stats_are_worked_out_for_results_saved_before_they_were_stored() {
+        let database = Database::open_in_memory().unwrap();
+        let id = database.save_result(&result()).unwrap();
+        database
+            .connection
+            .execute_batch("DELETE FROM key_stats; UPDATE results SET key_stats_saved = 0")
+            .unwrap();
+
+        assert_eq!(
+            database.load_key_stats().unwrap(),
+            KeyStats::from_keystrokes(&database.load_result(id).unwrap().keystrokes)
+        );
+    }
+
+    #[test]
+    fn quote_results_keep_their_quote_and_length() {
+        let database = Database::open_in_memory().unwrap();
+        let quote_result = ChallengeResult {
+            quote_id: Some(3),
+            ..result_with(ChallengeMode::Quote(QuoteLength::Long), 70.0, 1_000)
+        };
+        let id = database.save_result(&quote_result).unwrap();
+        database.save_result(&result()).unwrap();
+
+        assert_eq!(database.load_result(id).unwrap(), quote_result);
+
+        let query = HistoryQuery {
+            mode: ModeFilter::Quote(Some(QuoteLength::Long)),
+            ..Default::default()
+        };
+        let summaries = database.load_result_summaries(&query).unwrap();
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].id, id);
+    }
+
+    #[test]
+    fn results_keep_whether_they_stopped_on_errors() {
+        let database = Database::open_in_memory().unwrap();
+        let strict_result = ChallengeResult {
+            stop_on_error: StopOnError::Word,
+            ..result()
+        };
+        let id = database.save_result(&strict_result).unwrap();
+
+        assert_eq!(database.load_result(id).unwrap(), strict_result);
+    }
+
+    #[test]
+    fn custom_results_keep_their_text() {
+        let database = Database::open_in_memory().unwrap();
+        let custom_result = ChallengeResult {
+            custom_text: Some("some text of my own".to_string()),
+            ..result_with(ChallengeMode::Custom(5), 64.0, 1_000)
+        };
+        let id = database.save_result(&custom_result).unwrap();
+        database.save_result(&result()).unwrap();
+
+        assert_eq!(database.load_result(id).unwrap(), custom_result);
+
+        let query = HistoryQuery {
+            mode: ModeFilter::Custom,
+            ..Default::default()
+        };
+        let summaries = database.load_result_summaries(&query).unwrap();
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].id, id);
+    }
+
+    #[test]
+    fn adaptive_keystrokes_are_oldest_first_and_only_from_adaptive_results() {
+        let database = Database::open_in_memory().unwrap();
+        let adaptive_result = |expected| ChallengeResult {
+            custom_text: Some("tine lent".to_string()),
+            keystrokes: vec![Keystroke {
+                expected: Some(expected),
+                ..result().keystrokes[0]
+            }],
+            ..result_with(ChallengeMode::Adaptive(2), 40.0, 1_000)
+        };
+        let first = adaptive_result('t');
+        let second = adaptive_result('l');
+        let id = database.save_result(&first).unwrap();
+        database.save_result(&result()).unwrap();
+        database.save_result(&second).unwrap();
+
+        assert_eq!(database.load_result(id).unwrap(), first);
+        assert_eq!(
+            database.load_adaptive_keystrokes().unwrap(),
+            vec![first.keystrokes, second.keystrokes]
+        );
+    }
+}
@@ -1,3 +1,9 @@
+use crate::{
+    config::{app_config::AppConfig, config_file::ConfigFile},
+    features::words_challenge::{custom_text::CustomText, ghost::GhostSource},
+    storage::database::Database,
+};
+
 use super::app_page::AppPage;
 
 pub struct AppModel {
@@ -5,4 +11,19 @@ pub struct AppModel {
     pub app_done: bool,
 
     pub app_page: AppPage,
+
+    // where finished challenges are saved
+    pub database: Database,
+
+    // which earlier run new challenges race against
+    pub ghost_source: GhostSource,
+
+    pub config: AppConfig,
+    // where the config came from, it's loaded again whenever it changes
+    pub config_file: ConfigFile,
+    // why the config file couldn't be loaded again, the last good config is kept until it's fixed
+    pub config_error: Option<String>,
+
+    // text from a file or stdin to practise on, when the app was started with some
+    pub custom_text: Option<CustomText>,
 }
@@ -1,6 +1,19 @@
-use crate::features::words_challenge::words_challenge_model::WordsChallengeModel;
+use crate::features::{
+    history::history_model::HistoryModel, menu::menu_model::MenuModel,
+    quote_search::quote_search_model::QuoteSearchModel, replay::replay_model::ReplayModel, results::results_model::ResultsModel,
+    stats::stats_model::StatsModel,
+    words_challenge::words_challenge_model::WordsChallengeModel,
+};
 
+// only one page is alive at a time so the size difference between them doesn't matter
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug)]
 pub enum AppPage {
+    Menu(MenuModel),
     WordsChallenge(WordsChallengeModel),
+    Results(ResultsModel),
+    History(HistoryModel),
+    Replay(ReplayModel),
+    QuoteSearch(QuoteSearchModel),
+    Stats(StatsModel),
 }
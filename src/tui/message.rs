@@ -1,7 +1,44 @@
 use crossterm::event::KeyCode;
 
+use crate::features::words_challenge::challenge_settings::ChallengeSettings;
+
 pub enum Message {
     AppExit,
 
+    // Sent at a fixed rate so pages can update without waiting for input
+    Tick,
+
+    // Navigation, these can be sent from any page
+    OpenMenu,
+    OpenHistory,
+    OpenStats,
+    // Starts a new challenge with the given settings and seed
+    StartChallenge(ChallengeSettings, u64),
+    // Starts a challenge with the same words as a saved result, racing against its ghost
+    StartRace(i64),
+    // Starts typing the quote with the given id
+    StartQuote(u32),
+    // Starts typing the given custom text again
+    StartCustomText(String),
+    // Starts typing the next chunk of the custom text the app was started with
+    StartNextCustomChunk,
+    // Starts typing the given adaptive lesson again
+    StartAdaptiveText(String),
+    OpenQuoteSearch,
+
+    MenuInput(KeyCode),
+
     ChallengeLetterInput(KeyCode),
+    ChallengeFinished,
+
+    HistoryInput(KeyCode),
+
+    OpenReplay,
+    ReplayInput(KeyCode),
+    CloseReplay,
+
+    QuoteSearchInput(KeyCode),
+
+    // Switches what the keyboard heatmap on the results or stats is coloured by
+    SwitchHeatmap,
 }
@@ -1,6 +1,7 @@
 pub(crate) mod app_model;
 pub(crate) mod app_page;
 pub(crate) mod message;
+#[allow(clippy::module_inception)]
 pub(crate) mod tui;
 mod update;
 mod view;
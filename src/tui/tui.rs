@@ -1,43 +1,113 @@
+use std::time::{Duration, Instant};
+
 use color_eyre::Result;
-use crossterm::event::{self, KeyCode, KeyEventKind};
+use crossterm::event::{self, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::DefaultTerminal;
 
-use crate::features::words_challenge::words_challenge_model::WordsChallengeModel;
+use crate::{
+    config::{app_config::AppConfig, config_file::ConfigFile},
+    features::{
+        menu::menu_model::MenuModel,
+        words_challenge::{custom_text::CustomText, ghost::GhostSource},
+    },
+    storage::database::Database,
+};
 
 use super::{app_model::AppModel, app_page::AppPage, message::Message, update::update, view::view};
 
-pub fn run_tui() -> Result<()> {
+// How often a tick message is sent when no other events are happening
+const TICK_RATE: Duration = Duration::from_millis(100);
+
+// Starts on the menu, the first message can take the app straight to a challenge instead. The
+// database and config are opened before this so that any error with them is printed normally
+pub fn run_tui(
+    database: Database,
+    config_file: ConfigFile,
+    config: AppConfig,
+    custom_text: Option<CustomText>,
+    first_msg: Option<Message>,
+) -> Result<()> {
     let mut terminal = ratatui::init();
 
-    let words_challenge_model = WordsChallengeModel::generate(5);
-    let mut app_model = AppModel {
+    let app_model = AppModel {
         app_done: false,
-        app_page: AppPage::WordsChallenge(words_challenge_model),
+        app_page: AppPage::Menu(MenuModel::new(config.default_mode)),
+        database,
+        ghost_source: GhostSource::default(),
+        config,
+        config_file,
+        config_error: None,
+        custom_text,
     };
 
-    while !app_model.app_done {
-        terminal.draw(|frame| view(&app_model, frame))?;
+    // the terminal is put back however the app ends, otherwise an error saving or loading results
+    // would leave it in raw mode on the alternate screen. The app's own error is the one reported
+    let app_result = run_app(&mut terminal, app_model, first_msg);
+    let restore_result = ratatui::try_restore();
+    app_result?;
+    restore_result?;
 
-        let mut current_msg = handle_event(&app_model)?;
+    Ok(())
+}
 
+fn run_app(
+    terminal: &mut DefaultTerminal,
+    mut app_model: AppModel,
+    first_msg: Option<Message>,
+) -> Result<()> {
+    let mut last_tick = Instant::now();
+    let mut current_msg = first_msg;
+
+    loop {
         while current_msg.is_some() {
             (app_model, current_msg) = update(app_model, current_msg.unwrap())?;
         }
-    }
 
-    ratatui::try_restore()?;
+        if app_model.app_done {
+            return Ok(());
+        }
 
-    Ok(())
+        terminal.draw(|frame| view(&app_model, frame))?;
+
+        current_msg = handle_event(&app_model, &mut last_tick)?;
+    }
 }
 
-fn handle_event(app_model: &AppModel) -> Result<Option<Message>> {
+// Waits for the next terminal event until the next tick is due, ticks are checked first so that
+// a stream of key presses can't hold them back
+fn handle_event(app_model: &AppModel, last_tick: &mut Instant) -> Result<Option<Message>> {
+    if last_tick.elapsed() >= TICK_RATE {
+        *last_tick = Instant::now();
+        return Ok(Some(Message::Tick));
+    }
+
+    if !event::poll(TICK_RATE.saturating_sub(last_tick.elapsed()))? {
+        return Ok(None);
+    }
+
     if let event::Event::Key(key) = event::read()? {
-        // Handle any global key events
-        if key.kind == KeyEventKind::Press && key.code == KeyCode::Char('q') {
+        if key.kind != KeyEventKind::Press {
+            return Ok(None);
+        }
+
+        // Handle any global key events, letters are left alone so they can be typed. Searching
+        // for a quote takes escape to leave the search rather than quitting
+        let is_ctrl_c =
+            key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c');
+        let is_searching = matches!(app_model.app_page, AppPage::QuoteSearch(_));
+        let is_quit = key.code == app_model.config.keybindings.quit;
+        if (is_quit && !is_searching) || is_ctrl_c {
             return Ok(Some(Message::AppExit));
         }
 
         return match &app_model.app_page {
+            AppPage::Menu(model) => model.handle_event(key, &app_model.config.keybindings),
             AppPage::WordsChallenge(model) => model.handle_event(key),
+            AppPage::Results(model) => model.handle_event(key, &app_model.config.keybindings),
+            AppPage::History(model) => model.handle_event(key, &app_model.config.keybindings),
+            AppPage::Replay(model) => model.handle_event(key),
+            AppPage::QuoteSearch(model) => model.handle_event(key),
+            AppPage::Stats(model) => model.handle_event(key, &app_model.config.keybindings),
         };
     };
 
@@ -1,11 +1,283 @@
 use color_eyre::Result;
 
-use crate::features::words_challenge::words_challenge_update::words_challenge_update;
+use crate::features::{
+    history::{
+        history_model::HistoryModel, history_query::HistoryQuery, history_update::history_update,
+    },
+    menu::{menu_model::MenuModel, menu_update::menu_update},
+    quote_search::{quote_search_model::QuoteSearchModel, quote_search_update::quote_search_update},
+    replay::replay_update::replay_update,
+    results::results_update::results_update,
+    stats::{stats_model::StatsModel, stats_summary::StatsSummary, stats_update::stats_update},
+    words_challenge::{
+        challenge_mode::ChallengeMode,
+        ghost::{Ghost, GhostSource},
+        quote::Quote,
+        word_generator::WordGenerator, words_challenge_model::WordsChallengeModel,
+        words_challenge_update::words_challenge_update,
+    },
+};
 
 use super::{app_model::AppModel, app_page::AppPage, message::Message};
 
 pub fn update(model: AppModel, msg: Message) -> Result<(AppModel, Option<Message>)> {
+    // the tick carries on to the page afterwards, the config is only checked alongside it
+    let model = match msg {
+        Message::Tick if model.config_file.has_changed() => reload_config(model),
+        _ => model,
+    };
+
+    match msg {
+        Message::AppExit => {
+            return Ok((
+                AppModel {
+                    app_done: true,
+                    ..model
+                },
+                None,
+            ))
+        }
+        Message::OpenMenu => {
+            return Ok((
+                AppModel {
+                    app_page: AppPage::Menu(MenuModel::new(model.config.default_mode)),
+                    ..model
+                },
+                None,
+            ))
+        }
+        Message::OpenHistory => {
+            let query = HistoryQuery::default();
+            let history = HistoryModel::new(
+                query.clone(),
+                model.database.load_languages()?,
+                model.database.load_result_summaries(&query)?,
+            );
+            return Ok((
+                AppModel {
+                    app_page: AppPage::History(history),
+                    ..model
+                },
+                None,
+            ));
+        }
+        Message::OpenStats => {
+            let summary =
+                StatsSummary::from_results(&model.database.load_result_summaries(&HistoryQuery::default())?);
+            let key_stats = model.database.load_key_stats()?;
+            return Ok((
+                AppModel {
+                    app_page: AppPage::Stats(StatsModel::new(summary, key_stats)),
+                    ..model
+                },
+                None,
+            ));
+        }
+        Message::StartChallenge(settings, seed) => {
+            let ghost_id = match model.ghost_source {
+                GhostSource::Off => None,
+                GhostSource::PersonalBest => model.database.personal_best_id(&settings)?,
+                GhostSource::LastRun => model.database.last_result_id(&settings)?,
+            };
+            let ghost = match ghost_id {
+                Some(id) => Ghost::from_result(&model.database.load_result(id)?),
+                None => None,
+            };
+
+            let challenge = match settings.mode {
+                // the lesson is made up from how far adaptive practice has got, the seed only
+                // picks which words
+                ChallengeMode::Adaptive(num_words) => {
+                    let progress = model.database.load_adaptive_progress()?;
+                    WordsChallengeModel::from_adaptive_text(progress.lesson(num_words, seed))
+                        .with_adaptive_letters(progress.letters())
+                }
+                _ => WordsChallengeModel::new(
+                    settings.mode,
+                    WordGenerator::new(settings.word_band, seed),
+                ),
+            }
+            .with_ghost(ghost);
+            let challenge = configured_challenge(&model, challenge);
+            return Ok((
+                AppModel {
+                    app_page: AppPage::WordsChallenge(challenge),
+                    ..model
+                },
+                None,
+            ));
+        }
+        Message::StartQuote(quote_id) => {
+            let Some(quote) = Quote::by_id(quote_id) else {
+                return Ok((model, None));
+            };
+
+            let challenge = configured_challenge(&model, WordsChallengeModel::from_quote(quote));
+            return Ok((
+                AppModel {
+                    app_page: AppPage::WordsChallenge(challenge),
+                    ..model
+                },
+                None,
+            ));
+        }
+        Message::StartCustomText(text) => {
+            let challenge =
+                configured_challenge(&model, WordsChallengeModel::from_custom_text(text));
+            return Ok((
+                AppModel {
+                    app_page: AppPage::WordsChallenge(challenge),
+                    ..model
+                },
+                None,
+            ));
+        }
+        Message::StartAdaptiveText(text) => {
+            let progress = model.database.load_adaptive_progress()?;
+            let challenge = WordsChallengeModel::from_adaptive_text(text)
+                .with_adaptive_letters(progress.letters());
+            let challenge = configured_challenge(&model, challenge);
+            return Ok((
+                AppModel {
+                    app_page: AppPage::WordsChallenge(challenge),
+                    ..model
+                },
+                None,
+            ));
+        }
+        Message::StartNextCustomChunk => {
+            // results from custom text typed in an earlier session have no text to carry on with
+            let Some(custom_text) = model.custom_text else {
+                return Ok((
+                    AppModel {
+                        custom_text: None,
+                        ..model
+                    },
+                    Some(Message::OpenMenu),
+                ));
+            };
+
+            let (custom_text, chunk) = custom_text.next_chunk();
+            return Ok((
+                AppModel {
+                    custom_text: Some(custom_text),
+                    ..model
+                },
+                Some(Message::StartCustomText(chunk)),
+            ));
+        }
+        Message::OpenQuoteSearch => {
+            return Ok((
+                AppModel {
+                    app_page: AppPage::QuoteSearch(QuoteSearchModel::new()),
+                    ..model
+                },
+                None,
+            ))
+        }
+        Message::StartRace(id) => {
+            let result = model.database.load_result(id)?;
+            let challenge = WordsChallengeModel::same_text_as(&result)
+                .with_ghost(Ghost::from_result(&result));
+            // a race is typed under the raced result's stop on error, the ghost couldn't be
+            // matched otherwise
+            let challenge = configured_challenge(&model, challenge)
+                .with_stop_on_error(result.stop_on_error);
+            return Ok((
+                AppModel {
+                    app_page: AppPage::WordsChallenge(challenge),
+                    ..model
+                },
+                None,
+            ));
+        }
+        _ => {}
+    }
+
     match &model.app_page {
-        AppPage::WordsChallenge(challenge_model) => words_challenge_update(model, msg),
+        AppPage::Menu(_) => menu_update(model, msg),
+        AppPage::WordsChallenge(_) => words_challenge_update(model, msg),
+        AppPage::Results(_) => results_update(model, msg),
+        AppPage::History(_) => history_update(model, msg),
+        AppPage::Replay(_) => replay_update(model, msg),
+        AppPage::QuoteSearch(_) => quote_search_update(model, msg),
+        AppPage::Stats(_) => stats_update(model, msg),
+    }
+}
+
+// how every new challenge is typed comes from the config
+fn configured_challenge(model: &AppModel, challenge: WordsChallengeModel) -> WordsChallengeModel {
+    challenge
+        .with_backspace_policy(model.config.backspace_policy)
+        .with_stop_on_error(model.config.stop_on_error)
+        .with_fail_conditions(model.config.fail_conditions)
+}
+
+fn reload_config(mut model: AppModel) -> AppModel {
+    match model.config_file.load() {
+        Ok(config) => AppModel {
+            config,
+            config_error: None,
+            ..model
+        },
+        Err(error) => AppModel {
+            config_error: Some(format!("{:#}", error)),
+            ..model
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs::{self, File},
+        time::{Duration, SystemTime},
+    };
+
+    use crate::{
+        config::config_file::ConfigFile,
+        storage::database::Database,
+    };
+
+    use super::*;
+
+    #[test]
+    fn tick_applies_the_config_once_its_file_changes() {
+        let directory = std::env::temp_dir().join(format!("typee-reload-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("config.toml");
+        fs::write(&path, "keyboard = false").unwrap();
+
+        let mut config_file = ConfigFile::in_directory(directory.clone());
+        let config = config_file.load().unwrap();
+        let model = AppModel {
+            app_done: false,
+            app_page: AppPage::Menu(MenuModel::new(config.default_mode)),
+            database: Database::open_in_memory().unwrap(),
+            ghost_source: GhostSource::default(),
+            config,
+            config_file,
+            config_error: None,
+            custom_text: None,
+        };
+
+        let (model, _) = update(model, Message::Tick).unwrap();
+        assert!(!model.config.show_keyboard);
+
+        // the file is given a later modified time so the change is seen however coarse the clock is
+        fs::write(&path, "keyboard = true").unwrap();
+        File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(10))
+            .unwrap();
+
+        let (model, _) = update(model, Message::Tick).unwrap();
+
+        assert!(model.config.show_keyboard);
+        assert!(model.config_error.is_none());
+        assert!(!model.config_file.has_changed());
+        fs::remove_dir_all(&directory).unwrap();
     }
 }
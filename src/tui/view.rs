@@ -1,12 +1,36 @@
+use ratatui::prelude::*;
+use ratatui::widgets::Block;
 use ratatui::Frame;
 
+use crate::features::history::history_view::history_view;
+use crate::features::menu::menu_view::menu_view;
+use crate::features::quote_search::quote_search_view::quote_search_view;
+use crate::features::replay::replay_view::replay_view;
+use crate::features::results::results_view::results_view;
+use crate::features::stats::stats_view::stats_view;
 use crate::features::words_challenge::words_challenge_view::words_challenge_view;
 
 use super::app_model::AppModel;
 use super::app_page::AppPage;
 
 pub fn view(model: &AppModel, frame: &mut Frame) {
+    let theme = &model.config.theme;
+    let background = Style::new().fg(theme.foreground).bg(theme.background);
+    frame.render_widget(Block::new().style(background), frame.area());
+
     match &model.app_page {
-        AppPage::WordsChallenge(challenge) => words_challenge_view(challenge, frame),
+        AppPage::Menu(menu) => menu_view(
+            menu,
+            model.ghost_source,
+            &model.config,
+            model.config_error.as_deref(),
+            frame,
+        ),
+        AppPage::WordsChallenge(challenge) => words_challenge_view(challenge, &model.config, frame),
+        AppPage::Results(results) => results_view(results, &model.config, frame),
+        AppPage::History(history) => history_view(history, &model.config, frame),
+        AppPage::Replay(replay) => replay_view(replay, &model.config, frame),
+        AppPage::QuoteSearch(search) => quote_search_view(search, theme, frame),
+        AppPage::Stats(stats) => stats_view(stats, &model.config, frame),
     }
 }